@@ -1,15 +1,13 @@
 use anthropic_sdk::{
-    Client,
+    Client, ClientConfig,
     types::message::{Content, ContentBlock, Message, MessageRequest, Role},
 };
 use dotenvy::dotenv;
-use std::env;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
-    let api_key = env::var("ANTHROPIC_API_KEY")?;
-    let client = Client::new(api_key)?;
+    let client = Client::from_config(ClientConfig::from_env()?)?;
 
     // Agents often need to send a system prompt and a user message
     let request = MessageRequest::builder()
@@ -25,7 +23,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.messages().create(request).await?;
 
     for block in response.content {
-        if let ContentBlock::Text { text } = block {
+        if let ContentBlock::Text { text, .. } = block {
             println!("{}", text);
         }
     }
@@ -115,7 +115,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Response:");
         for block in response.content {
             match block {
-                anthropic_sdk::types::message::ContentBlock::Text { text } => {
+                anthropic_sdk::types::message::ContentBlock::Text { text, .. } => {
                     println!("{}", text)
                 }
                 anthropic_sdk::types::message::ContentBlock::Thinking { thinking, .. } => {
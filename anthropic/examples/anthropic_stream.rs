@@ -1,17 +1,14 @@
 use anthropic_sdk::{
-    Client,
+    Client, ClientConfig,
     types::message::{Content, ContentBlockDelta, Message, MessageRequest, Role, StreamEvent},
 };
 use dotenvy::dotenv;
 use futures_util::StreamExt;
-use std::env;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
-    let api_key = env::var("ANTHROPIC_API_KEY")?;
-
-    let client = Client::new(api_key)?;
+    let client = Client::from_config(ClientConfig::from_env()?)?;
 
     let request = MessageRequest::builder()
         .model("claude-3-opus-20240229")
@@ -1,14 +1,13 @@
 use anthropic_sdk::{
-    Client, InferenceContent, InferenceMessage, InferenceProvider, InferenceRequest, InferenceRole,
+    Client, ClientConfig, InferenceContent, InferenceMessage, InferenceProvider, InferenceRequest,
+    InferenceRole,
 };
 use dotenvy::dotenv;
-use std::env;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenv().ok();
-    let api_key = env::var("ANTHROPIC_API_KEY")?;
-    let client = Client::new(api_key)?;
+    let client = Client::from_config(ClientConfig::from_env()?)?;
 
     let request = InferenceRequest::builder()
         .model("claude-3-5-sonnet-20240620")
@@ -18,6 +17,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 text: "Hello from generic trait!".to_string(),
             }],
             tool_call_id: None,
+            cache_breakpoint: None,
         }])
         .max_tokens(1024)
         .build();
@@ -91,12 +91,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 text: user_prompt.clone(),
             }],
             tool_call_id: None,
+            cache_breakpoint: None,
         }],
         system: Some("You are a strict tool-using assistant.".to_string()),
+        system_cache_breakpoint: None,
         tools: Some(tools.clone()),
+        tool_choice: None,
+        parallel_tool_calls: None,
         temperature: None,
         max_tokens: Some(512),
         thinking_budget: None,
+        sampling: None,
+        unsupported_parameters: None,
+        response_schema: None,
     };
 
     dump_normalized_request("TURN 1", &req1);
@@ -122,11 +129,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 role: InferenceRole::User,
                 content: vec![InferenceContent::Text { text: user_prompt }],
                 tool_call_id: None,
+                cache_breakpoint: None,
             },
             InferenceMessage {
                 role: InferenceRole::Assistant,
                 content: res1.content.clone(),
                 tool_call_id: None,
+                cache_breakpoint: None,
             },
             InferenceMessage {
                 role: InferenceRole::Tool,
@@ -136,13 +145,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     is_error: false,
                 }],
                 tool_call_id: Some(tool_use_id.clone()),
+                cache_breakpoint: None,
             },
         ],
         system: Some("You are a strict tool-using assistant.".to_string()),
+        system_cache_breakpoint: None,
         tools: Some(tools),
+        tool_choice: None,
+        parallel_tool_calls: None,
         temperature: None,
         max_tokens: Some(256),
         thinking_budget: None,
+        sampling: None,
+        unsupported_parameters: None,
+        response_schema: None,
     };
 
     dump_normalized_request("TURN 2", &req2);
@@ -1,12 +1,12 @@
 use crate::config::ClientConfig;
 use crate::resources::messages::MessagesResource;
 use inference_sdk_core::SdkError;
-use reqwest::Client as HttpClient;
+use inference_sdk_core::http::{HttpTransport, ReqwestTransport};
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct Client {
-    pub(crate) http_client: HttpClient,
+    pub(crate) transport: Arc<dyn HttpTransport>,
     pub(crate) config: Arc<ClientConfig>,
 }
 
@@ -17,17 +17,19 @@ impl Client {
     }
 
     pub fn from_config(config: ClientConfig) -> Result<Self, SdkError> {
-        let mut builder = HttpClient::builder().default_headers(config.headers.clone());
-        if let Some(timeout) = config.timeout_policy.request_timeout {
-            builder = builder.timeout(timeout);
-        }
-
-        let http_client = builder
-            .build()
-            .map_err(|e| SdkError::ConfigError(format!("Failed to build HTTP client: {}", e)))?;
+        let transport: Arc<dyn HttpTransport> = match &config.transport {
+            Some(_) if !config.network.is_empty() => {
+                return Err(SdkError::ConfigError(
+                    "Network options apply only to the default transport; configure the custom transport instead"
+                        .to_string(),
+                ));
+            }
+            Some(transport) => transport.clone(),
+            None => Arc::new(ReqwestTransport::from_network_config(&config.network)?),
+        };
 
         Ok(Self {
-            http_client,
+            transport,
             config: Arc::new(config),
         })
     }
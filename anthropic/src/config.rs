@@ -1,11 +1,20 @@
-use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::SdkError;
-use inference_sdk_core::http::{RetryPolicy, TimeoutPolicy};
+use inference_sdk_core::env::{self, EnvLookup};
+use inference_sdk_core::http::{HttpTransport, RetryPolicy, TimeoutPolicy};
+use inference_sdk_core::{
+    AuthHeader, CredentialProvider, Middleware, MiddlewareStack, NetworkConfig, RateLimiter,
+    StaticCredentials,
+};
 
 const DEFAULT_BASE_URL: &str = "https://api.anthropic.com/v1";
+pub const API_KEY_ENV: &str = "ANTHROPIC_API_KEY";
+pub const BASE_URL_ENV: &str = "ANTHROPIC_BASE_URL";
+const API_KEY_HEADER: &str = "x-api-key";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
 pub const ANTHROPIC_VERSION: &str = "2023-06-01";
 pub const DEFAULT_THINKING_BETA_HEADER: &str = "output-128k-2025-02-19";
@@ -18,6 +27,11 @@ pub struct ClientConfig {
     pub(crate) retry_policy: RetryPolicy,
     pub(crate) timeout_policy: TimeoutPolicy,
     pub(crate) headers: HeaderMap,
+    pub(crate) credentials: Arc<dyn CredentialProvider>,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) middleware: MiddlewareStack,
+    pub(crate) transport: Option<Arc<dyn HttpTransport>>,
+    pub(crate) network: NetworkConfig,
     pub(crate) thinking_beta_header: Option<String>,
 }
 
@@ -25,36 +39,70 @@ pub struct ClientConfig {
 impl fmt::Debug for ClientConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ClientConfig")
-            .field("api_key", &"[REDACTED]")
+            .field("credentials", &self.credentials)
             .field("base_url", &self.base_url)
             .field("timeout", &self.timeout)
             .field("max_retries", &self.max_retries)
+            .field("middleware", &self.middleware)
+            .field("transport", &self.transport)
+            .field("network", &self.network)
             .finish()
     }
 }
 
 impl ClientConfig {
     pub fn new(api_key: String) -> Result<Self, SdkError> {
-        let header_value = HeaderValue::from_str(&api_key)
+        HeaderValue::from_str(&api_key)
             .map_err(|e| SdkError::ConfigError(format!("Invalid API key: {}", e)))?;
+        Ok(Self::from_credentials(StaticCredentials::new(api_key)))
+    }
 
+    /// Build a config whose API key is fetched from `credentials` before every HTTP attempt, so
+    /// rotated keys take effect without rebuilding the client.
+    pub fn from_credentials(credentials: impl CredentialProvider) -> Self {
         let mut headers = HeaderMap::new();
-        headers.insert("x-api-key", header_value);
         headers.insert(
             "anthropic-version",
             HeaderValue::from_static(ANTHROPIC_VERSION),
         );
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-        Ok(Self {
+        Self {
             base_url: DEFAULT_BASE_URL.to_string(),
             timeout: DEFAULT_TIMEOUT,
             max_retries: 2,
             retry_policy: RetryPolicy::default().with_max_retries(2),
             timeout_policy: TimeoutPolicy::default().with_request_timeout(DEFAULT_TIMEOUT),
             headers,
+            credentials: Arc::new(credentials),
+            rate_limiter: None,
+            middleware: MiddlewareStack::default(),
+            transport: None,
+            network: NetworkConfig::default(),
             thinking_beta_header: Some(DEFAULT_THINKING_BETA_HEADER.to_string()),
-        })
+        }
+    }
+
+    /// Build a config from `ANTHROPIC_API_KEY` and, when set, `ANTHROPIC_BASE_URL`.
+    pub fn from_env() -> Result<Self, SdkError> {
+        Self::from_env_lookup(&|name| std::env::var(name))
+    }
+
+    fn from_env_lookup(lookup: EnvLookup<'_>) -> Result<Self, SdkError> {
+        let api_key = env::required_var(lookup, API_KEY_ENV)?;
+        let mut config = Self::new(api_key).map_err(|e| env::named_error(API_KEY_ENV, e))?;
+        if let Some(base_url) = env::optional_var(lookup, BASE_URL_ENV)? {
+            config = config.with_base_url(base_url);
+        }
+        Ok(config)
+    }
+
+    /// The `x-api-key` header resolved from this config's credentials.
+    pub(crate) fn auth_header(&self) -> AuthHeader {
+        AuthHeader::new(
+            self.credentials.clone(),
+            HeaderName::from_static(API_KEY_HEADER),
+        )
     }
 
     pub fn with_max_retries(mut self, retries: u32) -> Self {
@@ -88,6 +136,40 @@ impl ClientConfig {
         self
     }
 
+    /// Send `key: value` with every request; per-request headers with the same name override it.
+    pub fn with_header(mut self, key: impl AsRef<str>, value: &str) -> Result<Self, SdkError> {
+        let name = HeaderName::from_bytes(key.as_ref().as_bytes())
+            .map_err(|e| SdkError::ConfigError(e.to_string()))?;
+        let value =
+            HeaderValue::from_str(value).map_err(|e| SdkError::ConfigError(e.to_string()))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Throttle requests through `limiter`; share one limiter between clients using the same key.
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Add a middleware layer around every HTTP attempt. Layers run in registration order.
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Send requests through `transport` instead of the default reqwest client.
+    pub fn with_transport(mut self, transport: impl HttpTransport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Proxy and TLS settings for the default transport; cannot be combined with `with_transport`.
+    pub fn with_network_config(mut self, network: NetworkConfig) -> Self {
+        self.network = network;
+        self
+    }
+
     /// Override the beta header used automatically when `thinking_budget` is set.
     pub fn with_thinking_beta_header(mut self, header: impl Into<String>) -> Self {
         self.thinking_beta_header = Some(header.into());
@@ -100,3 +182,31 @@ impl ClientConfig {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inference_sdk_core::env::lookup_from;
+
+    #[tokio::test]
+    async fn test_from_env_reads_key_and_base_url() {
+        let config = ClientConfig::from_env_lookup(&lookup_from(&[
+            (API_KEY_ENV, "sk-ant-test"),
+            (BASE_URL_ENV, "https://gateway.internal/v1"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.base_url, "https://gateway.internal/v1");
+        assert_eq!(config.auth_header().value().await.unwrap(), "sk-ant-test");
+
+        let config =
+            ClientConfig::from_env_lookup(&lookup_from(&[(API_KEY_ENV, "sk-ant-test")])).unwrap();
+        assert_eq!(config.base_url, DEFAULT_BASE_URL);
+    }
+
+    #[test]
+    fn test_from_env_names_missing_key() {
+        let err = ClientConfig::from_env_lookup(&lookup_from(&[])).unwrap_err();
+        assert!(err.to_string().contains(API_KEY_ENV), "{err}");
+    }
+}
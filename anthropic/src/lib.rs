@@ -11,29 +11,70 @@ pub use normalization::AnthropicRequestExt;
 // Re-export core types
 use futures_util::{StreamExt, future::BoxFuture};
 pub use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceMessage, InferenceProvider, InferenceRequest,
-    InferenceResult, InferenceRole, InferenceStream, RequestOptions, RetryNetworkRule, RetryPolicy,
-    RetryStatusRule, SdkError, StopReason, TimeoutPolicy, Usage,
+    ApiStatusError, CacheBreakpoint, CancellationToken, CannedResponse, ContentBlockKind,
+    CredentialProvider, EnvCredentials, FileCredentials, HttpTransport, InMemoryTransport,
+    InferenceContent, InferenceEvent, InferenceMessage, InferenceMetrics, InferenceProvider,
+    InferenceRequest, InferenceResult, InferenceRole, InferenceStream, MediaSource, Middleware,
+    MiddlewareStack, NetworkConfig, Next, RateLimitInfo, RateLimiter, RecordedRequest,
+    RefreshingCredentials, RequestOptions, ReqwestTransport, ResponseSchema, RetryNetworkRule,
+    RetryPolicy, RetryStatusRule, SamplingParams, SdkError, StaticCredentials, StopReason,
+    StreamMetrics, TimeoutPolicy, ToolChoice, UnsupportedParameterPolicy, Usage,
 };
+use inference_sdk_core::{metrics, telemetry};
+
+impl Client {
+    /// Adds provider-specific headers the normalized request needs (e.g. the thinking beta).
+    fn inference_options(
+        &self,
+        request: &types::message::MessageRequest,
+        options: Option<RequestOptions>,
+    ) -> Result<RequestOptions, SdkError> {
+        let mut opts = options.unwrap_or_default();
+        if request.thinking.is_some()
+            && let Some(beta_header) = &self.config.thinking_beta_header
+        {
+            opts = opts.beta(beta_header)?;
+        }
+        Ok(opts)
+    }
+}
 
 impl InferenceProvider for Client {
-    // Default complete() implementation from trait is used.
+    /// Uses the non-streaming Messages endpoint.
+    fn complete<'a>(
+        &'a self,
+        request: InferenceRequest,
+        options: Option<RequestOptions>,
+    ) -> BoxFuture<'a, Result<InferenceResult, SdkError>> {
+        let span = telemetry::inference_span("anthropic", "chat", &request.model);
+        Box::pin(telemetry::trace_complete(span, async move {
+            let response_schema = request.response_schema.clone();
+            let metrics = options.as_ref().and_then(|o| o.metrics.clone());
+            let anthropic_req = normalization::to_anthropic_request(request)?;
+            let opts = self.inference_options(&anthropic_req, options)?;
+
+            let response = self
+                .messages()
+                .create_with_options(anthropic_req, opts)
+                .await?;
+
+            let response_tool = response_schema.as_ref().map(|s| s.name.as_str());
+            let result = normalization::from_anthropic_response(response, response_tool)?;
+            Ok(result.finish(response_schema, metrics))
+        }))
+    }
 
     fn stream<'a>(
         &'a self,
         request: InferenceRequest,
         options: Option<RequestOptions>,
     ) -> BoxFuture<'a, Result<InferenceStream, SdkError>> {
-        Box::pin(async move {
+        let span = telemetry::inference_span("anthropic", "chat", &request.model);
+        Box::pin(telemetry::trace_stream(span, async move {
+            let response_tool = request.response_schema.as_ref().map(|s| s.name.clone());
+            let metrics = options.as_ref().and_then(|o| o.metrics.clone());
             let anthropic_req = normalization::to_anthropic_request(request)?;
-
-            // Handle beta headers (e.g. for thinking)
-            let mut opts = options.unwrap_or_default();
-            if anthropic_req.thinking.is_some()
-                && let Some(beta_header) = &self.config.thinking_beta_header
-            {
-                opts = opts.beta(beta_header)?;
-            }
+            let opts = self.inference_options(&anthropic_req, options)?;
 
             let stream = self
                 .messages()
@@ -42,6 +83,9 @@ impl InferenceProvider for Client {
 
             // Stateful adapter
             let mut adapter = normalization::AnthropicStreamAdapter::new();
+            if let Some(name) = response_tool {
+                adapter = adapter.with_response_tool(name);
+            }
 
             let mapped_stream = stream.map(
                 move |event_res: Result<types::message::StreamEvent, SdkError>| match event_res {
@@ -52,7 +96,7 @@ impl InferenceProvider for Client {
 
             let flat_stream = mapped_stream.flat_map(futures_util::stream::iter);
 
-            Ok(Box::pin(flat_stream) as InferenceStream)
-        })
+            Ok(metrics::measure_stream(Box::pin(flat_stream), metrics))
+        }))
     }
 }
@@ -1,12 +1,111 @@
 use crate::types;
 use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceRequest, InferenceRole, RequestOptions, SdkError,
-    StopReason,
+    ApiStatusError, CacheBreakpoint, ContentBlockKind, InferenceContent, InferenceEvent,
+    InferenceRequest, InferenceResult, InferenceRole, MediaSource, RequestOptions, ResponseSchema,
+    SdkError, StopReason, ToolChoice, Usage,
 };
 
+fn image_block(source: MediaSource) -> Result<types::message::ContentBlock, SdkError> {
+    let source = match source {
+        MediaSource::Base64 { media_type, data } => {
+            types::message::ImageSource::Base64 { media_type, data }
+        }
+        MediaSource::Url { url } => types::message::ImageSource::Url { url },
+        MediaSource::Text { .. } => {
+            return Err(SdkError::ConfigError(
+                "text media sources are only supported for documents".to_string(),
+            ));
+        }
+    };
+    Ok(types::message::ContentBlock::Image {
+        source,
+        cache_control: None,
+    })
+}
+
+fn document_block(source: MediaSource, title: Option<String>) -> types::message::ContentBlock {
+    let source = match source {
+        MediaSource::Base64 { media_type, data } => {
+            types::message::DocumentSource::Base64 { media_type, data }
+        }
+        MediaSource::Text { data } => types::message::DocumentSource::Text {
+            media_type: "text/plain".to_string(),
+            data,
+        },
+        MediaSource::Url { url } => types::message::DocumentSource::Url { url },
+    };
+    types::message::ContentBlock::Document {
+        source,
+        title,
+        cache_control: None,
+    }
+}
+
+fn cache_control(breakpoint: CacheBreakpoint) -> types::message::CacheControl {
+    match breakpoint {
+        CacheBreakpoint::Ephemeral => types::message::CacheControl::ephemeral(),
+        CacheBreakpoint::Extended => types::message::CacheControl::ephemeral().with_ttl("1h"),
+    }
+}
+
+/// Marks the last block that can carry `cache_control` (thinking blocks cannot).
+fn apply_cache_breakpoint(
+    blocks: &mut [types::message::ContentBlock],
+    breakpoint: Option<CacheBreakpoint>,
+) {
+    let Some(breakpoint) = breakpoint else {
+        return;
+    };
+    if let Some(slot) = blocks
+        .iter_mut()
+        .rev()
+        .find_map(types::message::ContentBlock::cache_control_mut)
+    {
+        *slot = Some(cache_control(breakpoint));
+    }
+}
+
+/// Rejects response schemas the forced-tool emulation cannot send.
+fn validate_response_schema(
+    schema: &ResponseSchema,
+    req: &InferenceRequest,
+) -> Result<(), SdkError> {
+    if req.tool_choice.is_some() {
+        return Err(SdkError::ConfigError(
+            "tool_choice cannot be combined with response_schema on Anthropic, which emulates structured output with a forced tool call".to_string(),
+        ));
+    }
+    if req.thinking_budget.is_some() {
+        return Err(SdkError::ConfigError(
+            "thinking_budget cannot be combined with response_schema on Anthropic, which rejects a forced tool call while extended thinking is enabled".to_string(),
+        ));
+    }
+    if req
+        .tools
+        .iter()
+        .flatten()
+        .any(|tool| tool.name == schema.name)
+    {
+        return Err(SdkError::ConfigError(format!(
+            "response_schema name '{}' collides with a tool of the same name",
+            schema.name
+        )));
+    }
+    if schema.schema.get("type") != Some(&serde_json::Value::from("object")) {
+        return Err(SdkError::ConfigError(
+            "response_schema must have \"type\": \"object\" at its root on Anthropic, which sends it as a tool input schema".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 pub fn to_anthropic_request(
     req: InferenceRequest,
 ) -> Result<types::message::MessageRequest, SdkError> {
+    if let Some(schema) = &req.response_schema {
+        validate_response_schema(schema, &req)?;
+    }
+
     let mut messages = Vec::new();
 
     for msg in req.messages {
@@ -14,12 +113,25 @@ pub fn to_anthropic_request(
             InferenceRole::User => {
                 let mut content_blocks = Vec::new();
                 for content in msg.content {
-                    if let InferenceContent::Text { text } = content {
-                        content_blocks.push(types::message::ContentBlock::Text { text });
+                    match content {
+                        InferenceContent::Text { text } => {
+                            content_blocks.push(types::message::ContentBlock::Text {
+                                text,
+                                cache_control: None,
+                            });
+                        }
+                        InferenceContent::Image { source } => {
+                            content_blocks.push(image_block(source)?);
+                        }
+                        InferenceContent::Document { source, title } => {
+                            content_blocks.push(document_block(source, title));
+                        }
+                        _ => {}
                     }
                 }
 
                 if !content_blocks.is_empty() {
+                    apply_cache_breakpoint(&mut content_blocks, msg.cache_breakpoint);
                     messages.push(types::message::Message {
                         role: types::message::Role::User,
                         content: types::message::Content::Blocks(content_blocks),
@@ -31,13 +143,17 @@ pub fn to_anthropic_request(
                 for content in msg.content {
                     match content {
                         InferenceContent::Text { text } => {
-                            content_blocks.push(types::message::ContentBlock::Text { text });
+                            content_blocks.push(types::message::ContentBlock::Text {
+                                text,
+                                cache_control: None,
+                            });
                         }
                         InferenceContent::ToolUse { id, name, input } => {
                             content_blocks.push(types::message::ContentBlock::ToolUse {
                                 id,
                                 name,
                                 input,
+                                cache_control: None,
                             });
                         }
                         InferenceContent::Thinking { content, signature } => {
@@ -46,11 +162,16 @@ pub fn to_anthropic_request(
                                 signature,
                             });
                         }
+                        InferenceContent::RedactedThinking { data } => {
+                            content_blocks
+                                .push(types::message::ContentBlock::RedactedThinking { data });
+                        }
                         _ => {}
                     }
                 }
 
                 if !content_blocks.is_empty() {
+                    apply_cache_breakpoint(&mut content_blocks, msg.cache_breakpoint);
                     messages.push(types::message::Message {
                         role: types::message::Role::Assistant,
                         content: types::message::Content::Blocks(content_blocks),
@@ -70,11 +191,13 @@ pub fn to_anthropic_request(
                             tool_use_id,
                             content: Some(types::message::ToolResultContent::Text(content)),
                             is_error: is_error.then_some(true),
+                            cache_control: None,
                         });
                     }
                 }
 
                 if !content_blocks.is_empty() {
+                    apply_cache_breakpoint(&mut content_blocks, msg.cache_breakpoint);
                     // Anthropic expects tool results to be sent as a user role message.
                     messages.push(types::message::Message {
                         role: types::message::Role::User,
@@ -85,16 +208,60 @@ pub fn to_anthropic_request(
         }
     }
 
-    let tools: Option<Vec<types::message::Tool>> = req.tools.map(|ts| {
+    let mut tools: Option<Vec<types::message::Tool>> = req.tools.map(|ts| {
         ts.into_iter()
             .map(|t| types::message::Tool {
                 name: t.name,
                 description: Some(t.description),
                 input_schema: t.input_schema,
+                cache_control: None,
             })
             .collect()
     });
 
+    let has_tools = tools.as_ref().is_some_and(|ts| !ts.is_empty());
+    let disable_parallel_tool_use = req
+        .parallel_tool_calls
+        .filter(|_| has_tools)
+        .map(|parallel| !parallel);
+    let mut tool_choice = match req.tool_choice {
+        Some(ToolChoice::Auto) => Some(types::message::ToolChoice::Auto {
+            disable_parallel_tool_use,
+        }),
+        Some(ToolChoice::None) => Some(types::message::ToolChoice::None),
+        Some(ToolChoice::Required) => Some(types::message::ToolChoice::Any {
+            disable_parallel_tool_use,
+        }),
+        Some(ToolChoice::Tool { name }) => Some(types::message::ToolChoice::Tool {
+            name,
+            disable_parallel_tool_use,
+        }),
+        // Anthropic only accepts the parallel toggle inside `tool_choice`.
+        None => disable_parallel_tool_use.map(|disable| types::message::ToolChoice::Auto {
+            disable_parallel_tool_use: Some(disable),
+        }),
+    };
+
+    // Anthropic has no native structured output: force exactly one call to a tool whose input
+    // schema is the response schema. `AnthropicStreamAdapter::with_response_tool` turns the
+    // call back into text.
+    if let Some(schema) = req.response_schema {
+        tools
+            .get_or_insert_with(Vec::new)
+            .push(types::message::Tool {
+                name: schema.name.clone(),
+                description: Some(schema.description.unwrap_or_else(|| {
+                    "Respond with JSON that matches this input schema.".to_string()
+                })),
+                input_schema: schema.schema,
+                cache_control: None,
+            });
+        tool_choice = Some(types::message::ToolChoice::Tool {
+            name: schema.name,
+            disable_parallel_tool_use: Some(true),
+        });
+    }
+
     let thinking = req
         .thinking_budget
         .map(|budget| types::message::ThinkingConfig {
@@ -102,20 +269,141 @@ pub fn to_anthropic_request(
             budget_tokens: budget,
         });
 
+    if req.system.is_none() && req.system_cache_breakpoint.is_some() {
+        return Err(SdkError::ConfigError(
+            "system_cache_breakpoint requires a system prompt".to_string(),
+        ));
+    }
+    let system = req.system.map(|text| match req.system_cache_breakpoint {
+        Some(breakpoint) => {
+            types::message::SystemPrompt::Blocks(vec![types::message::ContentBlock::Text {
+                text,
+                cache_control: Some(cache_control(breakpoint)),
+            }])
+        }
+        None => types::message::SystemPrompt::Text(text),
+    });
+
+    let sampling = req.sampling.unwrap_or_default();
+    let policy = req.unsupported_parameters.unwrap_or_default();
+    for (parameter, requested) in [
+        ("seed", sampling.seed.is_some()),
+        ("presence_penalty", sampling.presence_penalty.is_some()),
+        ("frequency_penalty", sampling.frequency_penalty.is_some()),
+    ] {
+        if requested {
+            policy.check("anthropic", parameter)?;
+        }
+    }
+
     Ok(types::message::MessageRequest::builder()
         .model(req.model)
         .messages(messages)
-        .maybe_system(req.system)
+        .maybe_system(system)
         .max_tokens(req.max_tokens.unwrap_or(8192))
         .maybe_temperature(req.temperature)
+        .maybe_top_p(sampling.top_p)
+        .maybe_top_k(sampling.top_k)
+        .maybe_stop_sequences(sampling.stop_sequences)
         .maybe_tools(tools)
+        .maybe_tool_choice(tool_choice)
         .maybe_thinking(thinking)
         .build())
 }
 
+/// Normalizes Anthropic usage so `input_tokens` also counts cache reads and writes.
+pub(crate) fn to_core_usage(usage: &types::message::Usage) -> Usage {
+    Usage {
+        input_tokens: usage
+            .input_tokens
+            .saturating_add(usage.cache_creation_input_tokens.unwrap_or(0))
+            .saturating_add(usage.cache_read_input_tokens.unwrap_or(0)),
+        output_tokens: usage.output_tokens,
+        cache_creation_input_tokens: usage.cache_creation_input_tokens,
+        cache_read_input_tokens: usage.cache_read_input_tokens,
+        reasoning_tokens: None,
+    }
+}
+
+/// Maps an Anthropic `stop_reason`. `structured_output` is set when the response was the
+/// emulated structured-output tool call, which is the answer rather than a tool request.
+fn to_stop_reason(reason: &str, structured_output: bool) -> StopReason {
+    match reason {
+        "end_turn" => StopReason::EndTurn,
+        "max_tokens" => StopReason::MaxTokens,
+        "tool_use" if structured_output => StopReason::EndTurn,
+        "tool_use" => StopReason::ToolUse,
+        "stop_sequence" => StopReason::StopSequence,
+        _ => StopReason::Unknown,
+    }
+}
+
+/// Normalizes a non-streaming `MessageResponse` into an `InferenceResult`.
+///
+/// `response_tool` names the tool emulating a `response_schema`; its input is returned as
+/// text, matching what `AnthropicStreamAdapter::with_response_tool` produces when streaming.
+pub fn from_anthropic_response(
+    response: types::message::MessageResponse,
+    response_tool: Option<&str>,
+) -> Result<InferenceResult, SdkError> {
+    let mut structured_output = false;
+    let mut content = Vec::with_capacity(response.content.len());
+    for block in response.content {
+        match block {
+            types::message::ContentBlock::Text { text, .. } => {
+                content.push(InferenceContent::Text { text });
+            }
+            types::message::ContentBlock::Thinking {
+                thinking,
+                signature,
+            } => content.push(InferenceContent::Thinking {
+                content: thinking,
+                signature,
+            }),
+            types::message::ContentBlock::RedactedThinking { data } => {
+                content.push(InferenceContent::RedactedThinking { data });
+            }
+            types::message::ContentBlock::ToolUse { name, input, .. }
+                if response_tool == Some(name.as_str()) =>
+            {
+                structured_output = true;
+                content.push(InferenceContent::Text {
+                    text: serde_json::to_string(&input)?,
+                });
+            }
+            types::message::ContentBlock::ToolUse {
+                id, name, input, ..
+            } => {
+                content.push(InferenceContent::ToolUse { id, name, input });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(InferenceResult {
+        content,
+        model: response.model,
+        stop_reason: response
+            .stop_reason
+            .map(|reason| to_stop_reason(&reason, structured_output)),
+        usage: to_core_usage(&response.usage),
+        structured_output: None,
+        response_schema: None,
+        rate_limit: response.rate_limit,
+        metrics: None,
+    })
+}
+
 #[derive(Default)]
 pub struct AnthropicStreamAdapter {
-    input_tokens: u32,
+    /// Usage from `message_start`, updated by the cumulative counters on `message_delta`.
+    usage: types::message::Usage,
+    /// Indices of blocks announced downstream; stops for other blocks are dropped.
+    open_blocks: Vec<u32>,
+    /// Name of the tool emulating a `response_schema`, if any.
+    response_tool: Option<String>,
+    /// Indices of blocks that carry the response tool's input, re-emitted as text.
+    response_blocks: Vec<u32>,
 }
 
 impl AnthropicStreamAdapter {
@@ -123,13 +411,20 @@ impl AnthropicStreamAdapter {
         Self::default()
     }
 
+    /// Re-emits calls to the structured-output tool `name` as text blocks, so the JSON reaches
+    /// consumers the same way it does from providers with native structured output.
+    pub fn with_response_tool(mut self, name: impl Into<String>) -> Self {
+        self.response_tool = Some(name.into());
+        self
+    }
+
     pub fn process_event(
         &mut self,
         event: types::message::StreamEvent,
     ) -> Vec<Result<InferenceEvent, SdkError>> {
         match event {
             types::message::StreamEvent::MessageStart { message } => {
-                self.input_tokens = message.usage.input_tokens;
+                self.usage = message.usage;
 
                 vec![Ok(InferenceEvent::MessageStart {
                     role: "assistant".to_string(),
@@ -137,7 +432,7 @@ impl AnthropicStreamAdapter {
                     provider_id: "anthropic".to_string(),
                 })]
             }
-            types::message::StreamEvent::ContentBlockDelta { delta, .. } => match delta {
+            types::message::StreamEvent::ContentBlockDelta { index, delta } => match delta {
                 types::message::ContentBlockDelta::TextDelta { text } => {
                     vec![Ok(InferenceEvent::MessageDelta { content: text })]
                 }
@@ -147,39 +442,134 @@ impl AnthropicStreamAdapter {
                 types::message::ContentBlockDelta::SignatureDelta { signature } => {
                     vec![Ok(InferenceEvent::ThinkingSignatureDelta { signature })]
                 }
+                types::message::ContentBlockDelta::InputJsonDelta { partial_json }
+                    if self.response_blocks.contains(&index) =>
+                {
+                    vec![Ok(InferenceEvent::MessageDelta {
+                        content: partial_json,
+                    })]
+                }
                 types::message::ContentBlockDelta::InputJsonDelta { partial_json } => {
                     vec![Ok(InferenceEvent::ToolCallDelta {
+                        index,
                         delta: partial_json,
                     })]
                 }
             },
             types::message::StreamEvent::ContentBlockStart {
-                content_block: types::message::ContentBlock::ToolUse { id, name, .. },
-                ..
-            } => vec![Ok(InferenceEvent::ToolCallStart { id, name })],
+                index,
+                content_block,
+            } => {
+                let mut events = Vec::new();
+                match content_block {
+                    types::message::ContentBlock::Text { text, .. } => {
+                        events.push(Ok(InferenceEvent::ContentBlockStart {
+                            index,
+                            kind: ContentBlockKind::Text,
+                        }));
+                        if !text.is_empty() {
+                            events.push(Ok(InferenceEvent::MessageDelta { content: text }));
+                        }
+                    }
+                    types::message::ContentBlock::Thinking { thinking, .. } => {
+                        events.push(Ok(InferenceEvent::ContentBlockStart {
+                            index,
+                            kind: ContentBlockKind::Thinking,
+                        }));
+                        if !thinking.is_empty() {
+                            events.push(Ok(InferenceEvent::ThinkingDelta { content: thinking }));
+                        }
+                    }
+                    types::message::ContentBlock::RedactedThinking { data } => {
+                        events.push(Ok(InferenceEvent::ContentBlockStart {
+                            index,
+                            kind: ContentBlockKind::RedactedThinking,
+                        }));
+                        events.push(Ok(InferenceEvent::RedactedThinking { data }));
+                    }
+                    types::message::ContentBlock::ToolUse { name, .. }
+                        if self.response_tool.as_deref() == Some(name.as_str()) =>
+                    {
+                        events.push(Ok(InferenceEvent::ContentBlockStart {
+                            index,
+                            kind: ContentBlockKind::Text,
+                        }));
+                        self.response_blocks.push(index);
+                    }
+                    types::message::ContentBlock::ToolUse { id, name, .. } => {
+                        events.push(Ok(InferenceEvent::ContentBlockStart {
+                            index,
+                            kind: ContentBlockKind::ToolUse,
+                        }));
+                        events.push(Ok(InferenceEvent::ToolCallStart { index, id, name }));
+                    }
+                    _ => return vec![],
+                }
+                self.open_blocks.push(index);
+                events
+            }
+            types::message::StreamEvent::ContentBlockStop { index } => {
+                match self.open_blocks.iter().position(|open| *open == index) {
+                    Some(position) => {
+                        self.open_blocks.remove(position);
+                        vec![Ok(InferenceEvent::ContentBlockStop { index })]
+                    }
+                    None => vec![],
+                }
+            }
             types::message::StreamEvent::MessageDelta { delta, usage } => {
-                let stop_reason = delta.stop_reason.map(|s| match s.as_str() {
-                    "end_turn" => StopReason::EndTurn,
-                    "max_tokens" => StopReason::MaxTokens,
-                    "tool_use" => StopReason::ToolUse,
-                    "stop_sequence" => StopReason::StopSequence,
-                    _ => StopReason::Unknown,
-                });
+                let stop_reason = delta
+                    .stop_reason
+                    .map(|s| to_stop_reason(&s, !self.response_blocks.is_empty()));
+
+                self.usage.output_tokens = usage.output_tokens;
+                if let Some(input_tokens) = usage.input_tokens {
+                    self.usage.input_tokens = input_tokens;
+                }
+                if usage.cache_creation_input_tokens.is_some() {
+                    self.usage.cache_creation_input_tokens = usage.cache_creation_input_tokens;
+                }
+                if usage.cache_read_input_tokens.is_some() {
+                    self.usage.cache_read_input_tokens = usage.cache_read_input_tokens;
+                }
 
                 vec![Ok(InferenceEvent::MessageEnd {
-                    input_tokens: self.input_tokens,
-                    output_tokens: usage.output_tokens,
+                    usage: to_core_usage(&self.usage),
                     stop_reason,
                 })]
             }
-            types::message::StreamEvent::Error { error } => {
-                vec![Err(SdkError::ProviderError(error.message))]
-            }
+            types::message::StreamEvent::Error { error } => vec![Err(stream_error(error))],
             _ => vec![],
         }
     }
 }
 
+/// Converts an in-stream `error` event into the `ApiStatusError` the same failure returns over
+/// HTTP, so `is_rate_limit`/`is_retryable` classify both alike. The status is the one Anthropic
+/// documents for the error type.
+fn stream_error(error: types::message::ErrorDetails) -> SdkError {
+    let status = match error.error_type.as_str() {
+        "invalid_request_error" => 400,
+        "authentication_error" => 401,
+        "permission_error" => 403,
+        "not_found_error" => 404,
+        "request_too_large" => 413,
+        "rate_limit_error" => 429,
+        "overloaded_error" => 529,
+        _ => 500,
+    };
+    let body = serde_json::json!({
+        "type": "error",
+        "error": { "type": error.error_type, "message": error.message },
+    });
+    SdkError::ApiStatusError(Box::new(ApiStatusError::new(
+        status,
+        None,
+        None,
+        body.to_string(),
+    )))
+}
+
 /// Anthropic-specific extensions for `RequestOptions`.
 pub trait AnthropicRequestExt {
     /// Add the `anthropic-beta` header to the request options.
@@ -197,8 +587,10 @@ impl AnthropicRequestExt for RequestOptions {
 mod tests {
     use super::*;
     use crate::types::message::{
-        MessageDeltaUsage, MessageResponse, StreamEvent, Usage as AnthropicUsage,
+        ContentBlock, ContentBlockDelta, MessageDelta, MessageDeltaUsage, MessageResponse,
+        StreamEvent, Usage as AnthropicUsage,
     };
+    use inference_sdk_core::{InferenceMessage, SamplingParams, Tool, UnsupportedParameterPolicy};
 
     #[test]
     fn test_anthropic_adapter_captures_usage() {
@@ -216,7 +608,9 @@ mod tests {
                 usage: AnthropicUsage {
                     input_tokens: 10,
                     output_tokens: 1,
+                    ..Default::default()
                 },
+                rate_limit: None,
             },
         };
 
@@ -227,32 +621,88 @@ mod tests {
         } else {
             panic!("Expected MessageStart");
         }
-        assert_eq!(adapter.input_tokens, 10);
+        assert_eq!(adapter.usage.input_tokens, 10);
 
         let delta_event = StreamEvent::MessageDelta {
             delta: crate::types::message::MessageDelta {
                 stop_reason: Some("end_turn".to_string()),
                 stop_sequence: None,
             },
-            usage: MessageDeltaUsage { output_tokens: 20 },
+            usage: MessageDeltaUsage {
+                output_tokens: 20,
+                ..Default::default()
+            },
         };
 
         let events = adapter.process_event(delta_event);
         assert_eq!(events.len(), 1);
-        if let Ok(InferenceEvent::MessageEnd {
-            input_tokens,
-            output_tokens,
-            stop_reason,
-        }) = &events[0]
-        {
-            assert_eq!(*input_tokens, 10);
-            assert_eq!(*output_tokens, 20);
+        if let Ok(InferenceEvent::MessageEnd { usage, stop_reason }) = &events[0] {
+            assert_eq!(usage.input_tokens, 10);
+            assert_eq!(usage.output_tokens, 20);
             assert_eq!(*stop_reason, Some(StopReason::EndTurn));
         } else {
             panic!("Expected MessageEnd");
         }
     }
 
+    #[test]
+    fn test_anthropic_adapter_folds_cache_tokens_into_input_tokens() {
+        let mut adapter = AnthropicStreamAdapter::new();
+        adapter.process_event(StreamEvent::MessageStart {
+            message: MessageResponse {
+                id: "msg_123".to_string(),
+                response_type: "message".to_string(),
+                role: crate::types::message::Role::Assistant,
+                content: vec![],
+                model: "claude-3-5-sonnet".to_string(),
+                stop_reason: None,
+                stop_sequence: None,
+                usage: AnthropicUsage {
+                    input_tokens: 4,
+                    output_tokens: 1,
+                    cache_creation_input_tokens: Some(100),
+                    cache_read_input_tokens: Some(0),
+                },
+                rate_limit: None,
+            },
+        });
+
+        // Newer API versions repeat cumulative input-side counters on message_delta.
+        let events = adapter.process_event(StreamEvent::MessageDelta {
+            delta: crate::types::message::MessageDelta {
+                stop_reason: Some("end_turn".to_string()),
+                stop_sequence: None,
+            },
+            usage: MessageDeltaUsage {
+                output_tokens: 7,
+                cache_read_input_tokens: Some(50),
+                ..Default::default()
+            },
+        });
+
+        let Ok(InferenceEvent::MessageEnd { usage, .. }) = &events[0] else {
+            panic!("Expected MessageEnd");
+        };
+        assert_eq!(usage.input_tokens, 154);
+        assert_eq!(usage.output_tokens, 7);
+        assert_eq!(usage.cache_creation_input_tokens, Some(100));
+        assert_eq!(usage.cache_read_input_tokens, Some(50));
+        assert_eq!(usage.total_tokens(), 161);
+    }
+
+    #[test]
+    fn test_to_core_usage_saturates_large_counts() {
+        let usage = to_core_usage(&AnthropicUsage {
+            input_tokens: u32::MAX - 1,
+            output_tokens: u32::MAX,
+            cache_creation_input_tokens: Some(10),
+            cache_read_input_tokens: Some(u32::MAX),
+        });
+
+        assert_eq!(usage.input_tokens, u32::MAX);
+        assert_eq!(usage.total_tokens(), u32::MAX);
+    }
+
     #[test]
     fn test_anthropic_adapter_emits_tool_argument_deltas() {
         let mut adapter = AnthropicStreamAdapter::new();
@@ -266,10 +716,47 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert!(matches!(
             events[0],
-            Ok(InferenceEvent::ToolCallDelta { ref delta }) if delta == "{\"city\":\"S"
+            Ok(InferenceEvent::ToolCallDelta { index: 0, ref delta }) if delta == "{\"city\":\"S"
+        ));
+    }
+
+    #[test]
+    fn test_anthropic_adapter_emits_block_boundaries_with_index() {
+        let mut adapter = AnthropicStreamAdapter::new();
+
+        let events = adapter.process_event(StreamEvent::ContentBlockStart {
+            index: 1,
+            content_block: types::message::ContentBlock::Text {
+                text: String::new(),
+                cache_control: None,
+            },
+        });
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            Ok(InferenceEvent::ContentBlockStart {
+                index: 1,
+                kind: ContentBlockKind::Text
+            })
+        ));
+
+        let events = adapter.process_event(StreamEvent::ContentBlockStop { index: 1 });
+        assert!(matches!(
+            events[..],
+            [Ok(InferenceEvent::ContentBlockStop { index: 1 })]
         ));
     }
 
+    #[test]
+    fn test_anthropic_adapter_drops_stop_for_unannounced_block() {
+        let mut adapter = AnthropicStreamAdapter::new();
+        assert!(
+            adapter
+                .process_event(StreamEvent::ContentBlockStop { index: 3 })
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_anthropic_adapter_emits_thinking_signature_deltas() {
         let mut adapter = AnthropicStreamAdapter::new();
@@ -286,6 +773,439 @@ mod tests {
             Ok(InferenceEvent::ThinkingSignatureDelta { ref signature }) if signature == "sig_abc"
         ));
     }
+
+    #[test]
+    fn test_anthropic_adapter_emits_redacted_thinking_block() {
+        let mut adapter = AnthropicStreamAdapter::new();
+        let mut events = adapter.process_event(StreamEvent::ContentBlockStart {
+            index: 0,
+            content_block: types::message::ContentBlock::RedactedThinking {
+                data: "EmwKAhgB".to_string(),
+            },
+        });
+        events.extend(adapter.process_event(StreamEvent::ContentBlockStop { index: 0 }));
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events[0],
+            Ok(InferenceEvent::ContentBlockStart {
+                index: 0,
+                kind: ContentBlockKind::RedactedThinking
+            })
+        ));
+        assert!(matches!(
+            events[1],
+            Ok(InferenceEvent::RedactedThinking { ref data }) if data == "EmwKAhgB"
+        ));
+        assert!(matches!(
+            events[2],
+            Ok(InferenceEvent::ContentBlockStop { index: 0 })
+        ));
+    }
+
+    /// A request with a single `role` message; tests set any other fields directly.
+    fn request(role: InferenceRole, content: Vec<InferenceContent>) -> InferenceRequest {
+        InferenceRequest::builder()
+            .model("test-model")
+            .messages(vec![InferenceMessage {
+                role,
+                content,
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }])
+            .max_tokens(128)
+            .build()
+    }
+
+    fn user_text(text: &str) -> InferenceRequest {
+        request(
+            InferenceRole::User,
+            vec![InferenceContent::Text {
+                text: text.to_string(),
+            }],
+        )
+    }
+
+    fn request_json(req: InferenceRequest) -> serde_json::Value {
+        let out = to_anthropic_request(req).expect("request should normalize");
+        serde_json::to_value(out).expect("request should serialize")
+    }
+
+    #[test]
+    fn images_and_documents_serialize_as_anthropic_blocks() {
+        let req = request(
+            InferenceRole::User,
+            vec![
+                InferenceContent::Text {
+                    text: "Compare these".to_string(),
+                },
+                InferenceContent::Image {
+                    source: MediaSource::Base64 {
+                        media_type: "image/png".to_string(),
+                        data: "iVBORw0KGgo=".to_string(),
+                    },
+                },
+                InferenceContent::Image {
+                    source: MediaSource::Url {
+                        url: "https://example.com/cat.jpg".to_string(),
+                    },
+                },
+                InferenceContent::Document {
+                    source: MediaSource::Base64 {
+                        media_type: "application/pdf".to_string(),
+                        data: "JVBERi0=".to_string(),
+                    },
+                    title: Some("report.pdf".to_string()),
+                },
+                InferenceContent::Document {
+                    source: MediaSource::Text {
+                        data: "plain notes".to_string(),
+                    },
+                    title: None,
+                },
+            ],
+        );
+
+        let json = request_json(req);
+        let blocks = &json["messages"][0]["content"];
+
+        assert_eq!(blocks[0]["type"], "text");
+        assert_eq!(blocks[1]["type"], "image");
+        assert_eq!(blocks[1]["source"]["type"], "base64");
+        assert_eq!(blocks[1]["source"]["media_type"], "image/png");
+        assert_eq!(blocks[1]["source"]["data"], "iVBORw0KGgo=");
+        assert_eq!(blocks[2]["source"]["type"], "url");
+        assert_eq!(blocks[2]["source"]["url"], "https://example.com/cat.jpg");
+        assert_eq!(blocks[3]["type"], "document");
+        assert_eq!(blocks[3]["source"]["media_type"], "application/pdf");
+        assert_eq!(blocks[3]["title"], "report.pdf");
+        assert_eq!(blocks[4]["source"]["type"], "text");
+        assert_eq!(blocks[4]["source"]["media_type"], "text/plain");
+        assert!(blocks[4].get("title").is_none());
+    }
+
+    #[test]
+    fn image_with_text_source_is_rejected() {
+        let req = request(
+            InferenceRole::User,
+            vec![InferenceContent::Image {
+                source: MediaSource::Text {
+                    data: "not an image".to_string(),
+                },
+            }],
+        );
+
+        assert!(to_anthropic_request(req).is_err());
+    }
+
+    fn tool_choice_json(
+        tool_choice: Option<ToolChoice>,
+        parallel_tool_calls: Option<bool>,
+    ) -> serde_json::Value {
+        let mut req = user_text("extract");
+        req.tools = Some(vec![Tool {
+            name: "record".to_string(),
+            description: "Record fields".to_string(),
+            input_schema: serde_json::json!({ "type": "object" }),
+        }]);
+        req.tool_choice = tool_choice;
+        req.parallel_tool_calls = parallel_tool_calls;
+        request_json(req)["tool_choice"].clone()
+    }
+
+    #[test]
+    fn omits_tool_choice_by_default() {
+        assert!(tool_choice_json(None, None).is_null());
+    }
+
+    #[test]
+    fn maps_each_choice_to_anthropic_shape() {
+        assert_eq!(
+            tool_choice_json(Some(ToolChoice::Auto), None),
+            serde_json::json!({ "type": "auto" })
+        );
+        assert_eq!(
+            tool_choice_json(Some(ToolChoice::None), None),
+            serde_json::json!({ "type": "none" })
+        );
+        assert_eq!(
+            tool_choice_json(Some(ToolChoice::Required), None),
+            serde_json::json!({ "type": "any" })
+        );
+        assert_eq!(
+            tool_choice_json(
+                Some(ToolChoice::Tool {
+                    name: "record".to_string()
+                }),
+                Some(false)
+            ),
+            serde_json::json!({
+                "type": "tool",
+                "name": "record",
+                "disable_parallel_tool_use": true
+            })
+        );
+    }
+
+    #[test]
+    fn parallel_toggle_without_choice_uses_auto() {
+        assert_eq!(
+            tool_choice_json(None, Some(false)),
+            serde_json::json!({ "type": "auto", "disable_parallel_tool_use": true })
+        );
+    }
+
+    fn sampling_request(
+        sampling: SamplingParams,
+        policy: Option<UnsupportedParameterPolicy>,
+    ) -> InferenceRequest {
+        let mut req = user_text("hi");
+        req.sampling = Some(sampling);
+        req.unsupported_parameters = policy;
+        req
+    }
+
+    #[test]
+    fn maps_supported_sampling_params() {
+        let sampling = SamplingParams::builder()
+            .stop_sequences(vec!["END".to_string()])
+            .top_p(0.9)
+            .top_k(40)
+            .build();
+        let json = request_json(sampling_request(sampling, None));
+
+        assert_eq!(json["stop_sequences"], serde_json::json!(["END"]));
+        assert!((json["top_p"].as_f64().unwrap() - 0.9).abs() < 1e-6);
+        assert_eq!(json["top_k"], 40);
+    }
+
+    #[test]
+    fn drops_unsupported_params_by_default() {
+        let sampling = SamplingParams::builder()
+            .seed(7)
+            .presence_penalty(0.5)
+            .build();
+        let json = request_json(sampling_request(sampling, None));
+
+        assert!(json.get("seed").is_none());
+        assert!(json.get("presence_penalty").is_none());
+    }
+
+    #[test]
+    fn error_policy_rejects_unsupported_params() {
+        let sampling = SamplingParams::builder().frequency_penalty(0.5).build();
+        let err = to_anthropic_request(sampling_request(
+            sampling,
+            Some(UnsupportedParameterPolicy::Error),
+        ))
+        .expect_err("frequency_penalty should be rejected");
+
+        assert!(matches!(
+            err,
+            SdkError::UnsupportedParameter { ref provider, ref parameter }
+                if provider == "anthropic" && parameter == "frequency_penalty"
+        ));
+    }
+
+    fn schema_request(tool_choice: Option<ToolChoice>) -> InferenceRequest {
+        let mut req = user_text("weather in Oslo?");
+        req.response_schema = Some(ResponseSchema::new(
+            "weather",
+            serde_json::json!({ "type": "object" }),
+        ));
+        req.tool_choice = tool_choice;
+        req
+    }
+
+    #[test]
+    fn response_schema_forces_single_tool_call() {
+        let json = request_json(schema_request(None));
+
+        assert_eq!(json["tools"][0]["name"], "weather");
+        assert_eq!(
+            json["tools"][0]["input_schema"],
+            serde_json::json!({ "type": "object" })
+        );
+        assert_eq!(
+            json["tool_choice"],
+            serde_json::json!({
+                "type": "tool",
+                "name": "weather",
+                "disable_parallel_tool_use": true
+            })
+        );
+    }
+
+    #[test]
+    fn response_schema_rejects_explicit_tool_choice() {
+        assert!(matches!(
+            to_anthropic_request(schema_request(Some(ToolChoice::Auto))),
+            Err(SdkError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn response_schema_rejects_thinking_budget() {
+        let mut req = schema_request(None);
+        req.thinking_budget = Some(1024);
+        let err = to_anthropic_request(req).expect_err("thinking should be rejected");
+        assert!(
+            matches!(&err, SdkError::ConfigError(message) if message.contains("thinking_budget")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn response_schema_rejects_tool_name_collision() {
+        let mut req = schema_request(None);
+        req.tools = Some(vec![Tool {
+            name: "weather".to_string(),
+            description: "Look up the weather".to_string(),
+            input_schema: serde_json::json!({ "type": "object" }),
+        }]);
+        let err = to_anthropic_request(req).expect_err("collision should be rejected");
+        assert!(
+            matches!(&err, SdkError::ConfigError(message) if message.contains("collides")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn response_schema_requires_object_root() {
+        for schema in [
+            serde_json::json!({ "type": "array", "items": { "type": "string" } }),
+            serde_json::json!({ "properties": {} }),
+        ] {
+            let mut req = schema_request(None);
+            req.response_schema = Some(ResponseSchema::new("weather", schema));
+            assert!(matches!(
+                to_anthropic_request(req),
+                Err(SdkError::ConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn adapter_reemits_response_tool_as_text() {
+        let mut adapter = AnthropicStreamAdapter::new().with_response_tool("weather");
+        let mut events = adapter.process_event(StreamEvent::ContentBlockStart {
+            index: 0,
+            content_block: ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "weather".to_string(),
+                input: serde_json::json!({}),
+                cache_control: None,
+            },
+        });
+        events.extend(adapter.process_event(StreamEvent::ContentBlockDelta {
+            index: 0,
+            delta: ContentBlockDelta::InputJsonDelta {
+                partial_json: "{\"celsius\":4}".to_string(),
+            },
+        }));
+        events.extend(adapter.process_event(StreamEvent::ContentBlockStop { index: 0 }));
+        events.extend(adapter.process_event(StreamEvent::MessageDelta {
+            delta: MessageDelta {
+                stop_reason: Some("tool_use".to_string()),
+                stop_sequence: None,
+            },
+            usage: MessageDeltaUsage {
+                output_tokens: 5,
+                ..Default::default()
+            },
+        }));
+
+        assert!(matches!(
+            events[0],
+            Ok(InferenceEvent::ContentBlockStart {
+                index: 0,
+                kind: ContentBlockKind::Text
+            })
+        ));
+        assert!(matches!(
+            events[1],
+            Ok(InferenceEvent::MessageDelta { ref content }) if content == "{\"celsius\":4}"
+        ));
+        assert!(matches!(
+            events[2],
+            Ok(InferenceEvent::ContentBlockStop { index: 0 })
+        ));
+        assert!(matches!(
+            events[3],
+            Ok(InferenceEvent::MessageEnd {
+                stop_reason: Some(StopReason::EndTurn),
+                ..
+            })
+        ));
+    }
+
+    fn cache_breakpoint_json(
+        system_cache_breakpoint: Option<CacheBreakpoint>,
+        message_cache_breakpoint: Option<CacheBreakpoint>,
+    ) -> serde_json::Value {
+        let mut req = request(
+            InferenceRole::Assistant,
+            vec![
+                InferenceContent::Text {
+                    text: "answer".to_string(),
+                },
+                InferenceContent::Thinking {
+                    content: "trailing thought".to_string(),
+                    signature: Some("sig".to_string()),
+                },
+            ],
+        );
+        req.system = Some("long system prompt".to_string());
+        req.system_cache_breakpoint = system_cache_breakpoint;
+        req.messages[0].cache_breakpoint = message_cache_breakpoint;
+        request_json(req)
+    }
+
+    #[test]
+    fn system_breakpoint_requires_a_system_prompt() {
+        let mut req = user_text("hi");
+        req.system_cache_breakpoint = Some(CacheBreakpoint::default());
+
+        assert!(matches!(
+            to_anthropic_request(req),
+            Err(SdkError::ConfigError(message)) if message.contains("system_cache_breakpoint")
+        ));
+    }
+
+    #[test]
+    fn system_stays_a_string_without_breakpoint() {
+        let json = cache_breakpoint_json(None, None);
+        assert_eq!(json["system"], "long system prompt");
+        assert!(
+            json["messages"][0]["content"][0]
+                .get("cache_control")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn system_breakpoint_becomes_cached_text_block() {
+        let json = cache_breakpoint_json(Some(CacheBreakpoint::Ephemeral), None);
+        assert_eq!(
+            json["system"],
+            serde_json::json!([{
+                "type": "text",
+                "text": "long system prompt",
+                "cache_control": { "type": "ephemeral" }
+            }])
+        );
+    }
+
+    #[test]
+    fn message_breakpoint_marks_last_cacheable_block() {
+        let json = cache_breakpoint_json(None, Some(CacheBreakpoint::Extended));
+        let blocks = &json["messages"][0]["content"];
+        assert_eq!(
+            blocks[0]["cache_control"],
+            serde_json::json!({ "type": "ephemeral", "ttl": "1h" })
+        );
+        assert!(blocks[1].get("cache_control").is_none());
+    }
 }
 
 #[cfg(test)]
@@ -304,6 +1224,9 @@ mod request_normalization_tests {
                         content: "deliberation".to_string(),
                         signature: Some("sig-123".to_string()),
                     },
+                    InferenceContent::RedactedThinking {
+                        data: "EmwKAhgB".to_string(),
+                    },
                     InferenceContent::ToolUse {
                         id: "toolu_1".to_string(),
                         name: "read_file".to_string(),
@@ -311,6 +1234,7 @@ mod request_normalization_tests {
                     },
                 ],
                 tool_call_id: None,
+                cache_breakpoint: None,
             }])
             .max_tokens(128)
             .build();
@@ -329,6 +1253,11 @@ mod request_normalization_tests {
                 ));
                 assert!(matches!(
                     &blocks[1],
+                    crate::types::message::ContentBlock::RedactedThinking { data }
+                    if data == "EmwKAhgB"
+                ));
+                assert!(matches!(
+                    &blocks[2],
                     crate::types::message::ContentBlock::ToolUse { id, name, .. }
                     if id == "toolu_1" && name == "read_file"
                 ));
@@ -355,6 +1284,7 @@ mod tool_result_request_shape_tests {
                     is_error: false,
                 }],
                 tool_call_id: Some("toolu_1".to_string()),
+                cache_breakpoint: None,
             }])
             .max_tokens(128)
             .build();
@@ -373,3 +1303,98 @@ mod tool_result_request_shape_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod response_normalization_tests {
+    use super::from_anthropic_response;
+    use crate::types::message::{ContentBlock, MessageResponse, Role, Usage};
+    use inference_sdk_core::{InferenceContent, StopReason};
+
+    fn response(content: Vec<ContentBlock>, stop_reason: &str) -> MessageResponse {
+        MessageResponse {
+            id: "msg_1".to_string(),
+            response_type: "message".to_string(),
+            role: Role::Assistant,
+            content,
+            model: "claude-test".to_string(),
+            stop_reason: Some(stop_reason.to_string()),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 4,
+                cache_read_input_tokens: Some(6),
+                ..Default::default()
+            },
+            rate_limit: None,
+        }
+    }
+
+    #[test]
+    fn maps_content_blocks_stop_reason_and_usage() {
+        let out = from_anthropic_response(
+            response(
+                vec![
+                    ContentBlock::Thinking {
+                        thinking: "hmm".to_string(),
+                        signature: Some("sig".to_string()),
+                    },
+                    ContentBlock::Text {
+                        text: "checking".to_string(),
+                        cache_control: None,
+                    },
+                    ContentBlock::ToolUse {
+                        id: "toolu_1".to_string(),
+                        name: "lookup".to_string(),
+                        input: serde_json::json!({ "q": "oslo" }),
+                        cache_control: None,
+                    },
+                ],
+                "tool_use",
+            ),
+            None,
+        )
+        .expect("response should normalize");
+
+        assert!(matches!(
+            &out.content[0],
+            InferenceContent::Thinking { content, signature: Some(sig) }
+                if content == "hmm" && sig == "sig"
+        ));
+        assert!(matches!(
+            &out.content[1],
+            InferenceContent::Text { text } if text == "checking"
+        ));
+        assert!(matches!(
+            &out.content[2],
+            InferenceContent::ToolUse { id, name, input }
+                if id == "toolu_1" && name == "lookup" && input["q"] == "oslo"
+        ));
+        assert_eq!(out.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(out.usage.input_tokens, 16);
+        assert_eq!(out.usage.output_tokens, 4);
+        assert_eq!(out.usage.cache_read_input_tokens, Some(6));
+    }
+
+    #[test]
+    fn response_tool_becomes_text_and_end_turn() {
+        let out = from_anthropic_response(
+            response(
+                vec![ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "weather".to_string(),
+                    input: serde_json::json!({ "celsius": 4 }),
+                    cache_control: None,
+                }],
+                "tool_use",
+            ),
+            Some("weather"),
+        )
+        .expect("response should normalize");
+
+        assert!(matches!(
+            &out.content[..],
+            [InferenceContent::Text { text }] if text == "{\"celsius\":4}"
+        ));
+        assert_eq!(out.stop_reason, Some(StopReason::EndTurn));
+    }
+}
@@ -1,13 +1,15 @@
 use crate::client::Client;
 use crate::types::message::{MessageRequest, MessageResponse, StreamEvent};
 use eventsource_stream::Eventsource;
-use futures_core::Stream;
 use futures_util::StreamExt;
 use inference_sdk_core::RequestOptions;
-use inference_sdk_core::SdkError;
-use inference_sdk_core::http::{RetryConfig, send_with_retry};
-use std::pin::Pin;
+use inference_sdk_core::http::{
+    EventStream, RetryConfig, StreamItemClass, cancellable_stream, idle_timeout_stream,
+    metered_bytes, read_json, retry_stream, send_with_retry,
+};
+use inference_sdk_core::{RateLimitInfo, SdkError};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 
 static REQUEST_DUMP_SEQ: AtomicU64 = AtomicU64::new(1);
 
@@ -50,19 +52,14 @@ impl MessagesResource {
         request: MessageRequest,
         options: RequestOptions,
     ) -> Result<MessageResponse, SdkError> {
-        let config = RetryConfig {
-            base_url: self.client.config.base_url.clone(),
-            endpoint: "/messages".to_string(),
-            retry_policy: self.client.config.retry_policy.clone(),
-            timeout_policy: self.client.config.timeout_policy.clone(),
-        };
+        let config = self.retry_config();
         maybe_dump_request("create", &self.client.config.base_url, &request);
         let response =
-            send_with_retry(&self.client.http_client, &config, &request, &options).await?;
-        response
-            .json::<MessageResponse>()
-            .await
-            .map_err(SdkError::from)
+            send_with_retry(&*self.client.transport, &config, &request, &options).await?;
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+        let mut body = read_json::<MessageResponse>(response, &options).await?;
+        body.rate_limit = rate_limit;
+        Ok(body)
     }
 
     /// Create a Message Stream
@@ -71,44 +68,103 @@ impl MessagesResource {
     pub async fn create_stream(
         &self,
         request: MessageRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, SdkError>> + Send + 'static>>, SdkError>
-    {
+    ) -> Result<EventStream<StreamEvent>, SdkError> {
         self.create_stream_with_options(request, RequestOptions::default())
             .await
     }
 
     /// Create a Message Stream with custom options
+    ///
+    /// With `RetryPolicy::retry_stream_before_first_event`, a stream that fails (connection
+    /// reset, or an `overloaded_error`/`api_error` event) before its first content block is
+    /// transparently re-issued.
     pub async fn create_stream_with_options(
         &self,
         mut request: MessageRequest,
         options: RequestOptions,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, SdkError>> + Send + 'static>>, SdkError>
-    {
+    ) -> Result<EventStream<StreamEvent>, SdkError> {
         request.stream = Some(true);
 
-        let config = RetryConfig {
+        let config = self.retry_config();
+        maybe_dump_request("create_stream", &self.client.config.base_url, &request);
+        let started_at = Instant::now();
+        let stream = open_stream(&self.client, &config, &request, &options).await?;
+
+        let client = self.client.clone();
+        let reopen_config = config.clone();
+        let stream = retry_stream(
+            &config,
+            &options,
+            started_at,
+            stream,
+            classify_stream_event,
+            move |options| {
+                let client = client.clone();
+                let config = reopen_config.clone();
+                let request = request.clone();
+                Box::pin(async move { open_stream(&client, &config, &request, &options).await })
+            },
+        );
+        Ok(cancellable_stream(stream, &options))
+    }
+
+    fn retry_config(&self) -> RetryConfig {
+        RetryConfig {
             base_url: self.client.config.base_url.clone(),
             endpoint: "/messages".to_string(),
+            default_headers: self.client.config.headers.clone(),
+            auth: Some(self.client.config.auth_header()),
             retry_policy: self.client.config.retry_policy.clone(),
             timeout_policy: self.client.config.timeout_policy.clone(),
-        };
-        maybe_dump_request("create_stream", &self.client.config.base_url, &request);
-        let response =
-            send_with_retry(&self.client.http_client, &config, &request, &options).await?;
+            rate_limiter: self.client.config.rate_limiter.clone(),
+            middleware: self.client.config.middleware.clone(),
+        }
+    }
+}
+
+async fn open_stream(
+    client: &Client,
+    config: &RetryConfig,
+    request: &MessageRequest,
+    options: &RequestOptions,
+) -> Result<EventStream<StreamEvent>, SdkError> {
+    let response = send_with_retry(&*client.transport, config, request, options).await?;
 
-        let stream = response.bytes_stream().eventsource();
+    let stream = metered_bytes(response.bytes_stream(), options).eventsource();
 
-        let mapped_stream = stream.map(|event_result| match event_result {
-            Ok(event) => {
-                if event.event == "ping" {
-                    return Ok(StreamEvent::Ping);
-                }
-                serde_json::from_str::<StreamEvent>(&event.data)
-                    .map_err(SdkError::SerializationError)
+    let mapped_stream = stream.map(|event_result| match event_result {
+        Ok(event) => {
+            if event.event == "ping" {
+                return Ok(StreamEvent::Ping);
             }
-            Err(e) => Err(SdkError::StreamError(e.to_string())),
-        });
+            serde_json::from_str::<StreamEvent>(&event.data).map_err(SdkError::SerializationError)
+        }
+        Err(e) => Err(SdkError::StreamError(e.to_string())),
+    });
+
+    Ok(idle_timeout_stream(
+        Box::pin(mapped_stream),
+        config,
+        options,
+    ))
+}
 
-        Ok(Box::pin(mapped_stream))
+/// `message_start` and pings precede content; transport failures, stalls, and transient in-stream
+/// errors are worth re-issuing.
+fn classify_stream_event(item: &Result<StreamEvent, SdkError>) -> StreamItemClass {
+    match item {
+        Ok(StreamEvent::MessageStart { .. } | StreamEvent::Ping) => StreamItemClass::Preamble,
+        Ok(StreamEvent::Error { error })
+            if matches!(
+                error.error_type.as_str(),
+                "overloaded_error" | "api_error" | "rate_limit_error"
+            ) =>
+        {
+            StreamItemClass::Retryable
+        }
+        Err(SdkError::StreamError(_) | SdkError::StreamIdleTimeout { .. }) => {
+            StreamItemClass::Retryable
+        }
+        _ => StreamItemClass::Content,
     }
 }
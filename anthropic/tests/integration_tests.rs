@@ -1,10 +1,11 @@
 use anthropic_sdk::AnthropicRequestExt;
-use anthropic_sdk::RequestOptions;
 use anthropic_sdk::{
     Client, ClientConfig,
-    types::message::{Content, ContentBlock, Message, MessageRequest, Role},
+    types::message::{Content, ContentBlock, Message, MessageRequest, Role, StreamEvent},
 };
+use anthropic_sdk::{RequestOptions, RetryPolicy, SdkError, TimeoutPolicy};
 use serde_json::json;
+use std::time::Duration;
 use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -57,7 +58,7 @@ async fn test_create_message() {
 
     assert_eq!(response.id, "msg_123");
     match &response.content[0] {
-        ContentBlock::Text { text } => assert_eq!(text, "Hello, world!"),
+        ContentBlock::Text { text, .. } => assert_eq!(text, "Hello, world!"),
         _ => panic!("Unexpected content type"),
     }
 }
@@ -217,8 +218,91 @@ async fn test_retry_exhaustion() {
 
     let result = client.messages().create(request).await;
     assert!(result.is_err());
-    let err = result.unwrap_err().to_string();
-    assert!(err.contains("500"), "Error should contain status code 500");
+    let err = result.unwrap_err();
+    assert!(
+        err.to_string().contains("500"),
+        "Error should contain status code 500"
+    );
+    match err {
+        SdkError::RetriesExhausted {
+            attempts,
+            last_error,
+        } => {
+            assert_eq!(attempts, 2);
+            assert_eq!(last_error.api_status().map(|e| e.status), Some(500));
+        }
+        other => panic!("expected RetriesExhausted, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_request_timeout_is_distinguished() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri())
+        .with_timeout(Duration::from_millis(50))
+        .with_max_retries(0);
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+
+    let err = client.messages().create(request).await.unwrap_err();
+    assert!(
+        matches!(err, SdkError::RequestTimeout { timeout } if timeout == Duration::from_millis(50)),
+        "expected RequestTimeout, got {err:?}"
+    );
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_total_timeout_budget_is_distinguished() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "5"))
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri())
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_max_retries(3)
+                .with_max_delay(Duration::from_secs(5)),
+        )
+        .with_timeout_policy(TimeoutPolicy::default().with_total_timeout(Duration::from_secs(1)));
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+
+    let err = client.messages().create(request).await.unwrap_err();
+    assert!(
+        matches!(err, SdkError::TotalTimeoutExceeded { budget, .. } if budget == Duration::from_secs(1)),
+        "expected TotalTimeoutExceeded, got {err:?}"
+    );
+    assert!(!err.is_retryable());
 }
 
 #[tokio::test]
@@ -234,3 +318,366 @@ async fn test_debug_redacts_api_key() {
         "Debug output should show [REDACTED]"
     );
 }
+
+#[tokio::test]
+async fn test_inference_complete_uses_non_streaming_endpoint() {
+    use anthropic_sdk::{
+        InferenceContent, InferenceMessage, InferenceProvider, InferenceRequest, InferenceRole,
+    };
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_complete",
+            "type": "message",
+            "role": "assistant",
+            "content": [{ "type": "text", "text": "Hello from JSON" }],
+            "model": "claude-3-opus-20240229",
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": { "input_tokens": 7, "output_tokens": 3 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri());
+    let client = Client::from_config(config).unwrap();
+
+    let request = InferenceRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![InferenceMessage {
+            role: InferenceRole::User,
+            content: vec![InferenceContent::Text {
+                text: "Hi".to_string(),
+            }],
+            tool_call_id: None,
+            cache_breakpoint: None,
+        }])
+        .max_tokens(64)
+        .build();
+
+    let result = client
+        .complete(request, None)
+        .await
+        .expect("complete should succeed");
+
+    assert_eq!(result.text(), "Hello from JSON");
+    assert_eq!(result.usage.input_tokens, 7);
+    assert_eq!(result.usage.output_tokens, 3);
+}
+
+#[tokio::test]
+async fn test_rate_limit_error_is_structured() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("request-id", "req_abc")
+                .insert_header("retry-after", "7")
+                .insert_header("anthropic-ratelimit-requests-limit", "50")
+                .insert_header("anthropic-ratelimit-requests-remaining", "0")
+                .set_body_json(json!({
+                    "type": "error",
+                    "error": { "type": "rate_limit_error", "message": "Number of requests exceeded" }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri())
+        .with_max_retries(0);
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+
+    let err = client.messages().create(request).await.unwrap_err();
+    assert!(err.is_rate_limit());
+    assert!(err.is_retryable());
+
+    let status = err.api_status().expect("structured API error");
+    assert_eq!(status.status, 429);
+    assert_eq!(status.error_type.as_deref(), Some("rate_limit_error"));
+    assert_eq!(status.message, "Number of requests exceeded");
+    assert_eq!(status.request_id.as_deref(), Some("req_abc"));
+    assert_eq!(status.retry_after, Some(std::time::Duration::from_secs(7)));
+    let rate_limit = status.rate_limit.as_ref().expect("rate-limit headers");
+    assert_eq!(rate_limit.requests_limit, Some(50));
+    assert!(rate_limit.is_exhausted());
+}
+
+fn sse(events: &[serde_json::Value]) -> String {
+    events
+        .iter()
+        .map(|event| {
+            format!(
+                "event: {}\ndata: {}\n\n",
+                event["type"].as_str().unwrap(),
+                event
+            )
+        })
+        .collect()
+}
+
+fn message_start() -> serde_json::Value {
+    json!({
+        "type": "message_start",
+        "message": {
+            "id": "msg_stream",
+            "type": "message",
+            "role": "assistant",
+            "content": [],
+            "model": "claude-3-opus-20240229",
+            "stop_reason": null,
+            "stop_sequence": null,
+            "usage": { "input_tokens": 3, "output_tokens": 0 }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_stream_retried_after_overloaded_error_before_content() {
+    use futures_util::StreamExt;
+
+    let mock_server = MockServer::start().await;
+
+    let overloaded = sse(&[
+        message_start(),
+        json!({ "type": "error", "error": { "type": "overloaded_error", "message": "Overloaded" } }),
+    ]);
+    let ok = sse(&[
+        message_start(),
+        json!({ "type": "content_block_start", "index": 0, "content_block": { "type": "text", "text": "" } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "text_delta", "text": "Hello" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+        json!({ "type": "message_delta", "delta": { "stop_reason": "end_turn", "stop_sequence": null }, "usage": { "output_tokens": 1 } }),
+        json!({ "type": "message_stop" }),
+    ]);
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(overloaded, "text/event-stream"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(ok, "text/event-stream"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri())
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_base_delay(Duration::from_millis(1))
+                .with_jitter(Duration::ZERO)
+                .with_stream_retry(true),
+        );
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+
+    let events: Vec<_> = client
+        .messages()
+        .create_stream(request)
+        .await
+        .expect("stream should open")
+        .collect()
+        .await;
+
+    assert!(events.iter().all(Result::is_ok), "{events:?}");
+    let starts = events
+        .iter()
+        .filter(|e| matches!(e, Ok(StreamEvent::MessageStart { .. })))
+        .count();
+    assert_eq!(starts, 1);
+    assert!(matches!(events.last(), Some(Ok(StreamEvent::MessageStop))));
+}
+
+#[tokio::test]
+async fn test_stream_metrics_record_retries_bytes_and_first_token() {
+    use anthropic_sdk::{
+        InferenceContent, InferenceMessage, InferenceProvider, InferenceRequest, InferenceResult,
+        InferenceRole, StreamMetrics,
+    };
+
+    let mock_server = MockServer::start().await;
+
+    let ok = sse(&[
+        message_start(),
+        json!({ "type": "content_block_start", "index": 0, "content_block": { "type": "text", "text": "" } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "text_delta", "text": "Hello" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+        json!({ "type": "message_delta", "delta": { "stop_reason": "end_turn", "stop_sequence": null }, "usage": { "output_tokens": 4 } }),
+        json!({ "type": "message_stop" }),
+    ]);
+
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(ok.clone(), "text/event-stream"))
+        .mount(&mock_server)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url(mock_server.uri())
+        .with_retry_policy(
+            RetryPolicy::default()
+                .with_base_delay(Duration::from_millis(1))
+                .with_jitter(Duration::ZERO),
+        );
+    let client = Client::from_config(config).unwrap();
+
+    let request = InferenceRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![InferenceMessage {
+            role: InferenceRole::User,
+            content: vec![InferenceContent::Text {
+                text: "Hi".to_string(),
+            }],
+            tool_call_id: None,
+            cache_breakpoint: None,
+        }])
+        .max_tokens(64)
+        .build();
+
+    let metrics = StreamMetrics::new();
+    let stream = client
+        .stream(
+            request,
+            Some(RequestOptions::new().with_metrics(metrics.clone())),
+        )
+        .await
+        .expect("stream should open");
+    assert_eq!(metrics.snapshot().time_to_first_token, None);
+
+    let result = InferenceResult::from_stream(stream).await.unwrap();
+    assert_eq!(result.text(), "Hello");
+
+    let recorded = metrics.snapshot();
+    assert!(recorded.started_at.is_some());
+    assert_eq!(recorded.retries, 1);
+    assert_eq!(recorded.bytes_received, ok.len() as u64);
+    assert_eq!(recorded.output_tokens, Some(4));
+    let first_token = recorded.time_to_first_token.expect("first token");
+    assert!(recorded.time_to_first_event.unwrap() <= first_token);
+    assert!(recorded.duration.unwrap() >= first_token);
+}
+
+#[tokio::test]
+async fn test_stream_through_in_memory_transport() {
+    use anthropic_sdk::{CannedResponse, InMemoryTransport};
+    use futures_util::StreamExt;
+
+    let transport = InMemoryTransport::new().with_response(CannedResponse::event_stream(sse(&[
+        message_start(),
+        json!({ "type": "content_block_start", "index": 0, "content_block": { "type": "text", "text": "" } }),
+        json!({ "type": "content_block_delta", "index": 0, "delta": { "type": "text_delta", "text": "Hi" } }),
+        json!({ "type": "content_block_stop", "index": 0 }),
+        json!({ "type": "message_stop" }),
+    ])));
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_header("x-tenant", "acme")
+        .unwrap()
+        .with_transport(transport.clone());
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+    let events: Vec<_> = client
+        .messages()
+        .create_stream(request)
+        .await
+        .expect("stream should open")
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 5, "{events:?}");
+    assert!(events.iter().all(Result::is_ok));
+    let sent = &transport.requests()[0];
+    assert_eq!(sent.headers["x-api-key"], "test-key");
+    assert_eq!(sent.headers["anthropic-version"], "2023-06-01");
+    assert_eq!(sent.headers["x-tenant"], "acme");
+    let body: serde_json::Value = serde_json::from_slice(&sent.body).unwrap();
+    assert_eq!(body["stream"], true);
+}
+
+#[tokio::test]
+async fn test_requests_are_sent_through_configured_proxy() {
+    use anthropic_sdk::NetworkConfig;
+
+    let proxy = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("proxy-authorization", "Basic c3ZjOnMzY3JldA=="))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_proxy",
+            "type": "message",
+            "role": "assistant",
+            "content": [{ "type": "text", "text": "via proxy" }],
+            "model": "claude-3-opus-20240229",
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": { "input_tokens": 1, "output_tokens": 2 }
+        })))
+        .expect(1)
+        .mount(&proxy)
+        .await;
+
+    let config = ClientConfig::new("test-key".to_string())
+        .unwrap()
+        .with_base_url("http://api.anthropic.invalid/v1")
+        .with_network_config(
+            NetworkConfig::new()
+                .with_proxy(proxy.uri())
+                .with_proxy_auth("svc", "s3cret"),
+        );
+    let client = Client::from_config(config).unwrap();
+
+    let request = MessageRequest::builder()
+        .model("claude-3-opus-20240229")
+        .messages(vec![Message {
+            role: Role::User,
+            content: Content::Text("Hi".to_string()),
+        }])
+        .build();
+    let response = client.messages().create(request).await.unwrap();
+    assert_eq!(response.id, "msg_proxy");
+}
@@ -3,7 +3,9 @@ use anthropic_sdk::types::message::{
     ContentBlock, ContentBlockDelta, ErrorDetails, MessageDelta, MessageDeltaUsage,
     MessageResponse, Role, StreamEvent, Usage,
 };
-use inference_sdk_core::{InferenceEvent, SdkError, StopReason, validate_event_sequence};
+use inference_sdk_core::{
+    ContentBlockKind, InferenceEvent, SdkError, StopReason, validate_event_sequence,
+};
 use serde_json::json;
 
 #[test]
@@ -23,7 +25,9 @@ fn test_anthropic_provider_contract_tool_stream_order_and_message_end() {
             usage: Usage {
                 input_tokens: 13,
                 output_tokens: 0,
+                ..Default::default()
             },
+            rate_limit: None,
         },
     }));
 
@@ -33,6 +37,7 @@ fn test_anthropic_provider_contract_tool_stream_order_and_message_end() {
             id: "call_1".to_string(),
             name: "weather".to_string(),
             input: json!({}),
+            cache_control: None,
         },
     }));
 
@@ -48,29 +53,52 @@ fn test_anthropic_provider_contract_tool_stream_order_and_message_end() {
             partial_json: "F\"}".to_string(),
         },
     }));
+    out.extend(adapter.process_event(StreamEvent::ContentBlockStop { index: 0 }));
 
     out.extend(adapter.process_event(StreamEvent::MessageDelta {
         delta: MessageDelta {
             stop_reason: Some("tool_use".to_string()),
             stop_sequence: None,
         },
-        usage: MessageDeltaUsage { output_tokens: 21 },
+        usage: MessageDeltaUsage {
+            output_tokens: 21,
+            ..Default::default()
+        },
     }));
 
     let events: Vec<InferenceEvent> = out.into_iter().collect::<Result<_, _>>().unwrap();
     validate_event_sequence(&events).expect("event sequence must satisfy core contract");
 
     assert!(matches!(events[0], InferenceEvent::MessageStart { .. }));
-    assert!(matches!(events[1], InferenceEvent::ToolCallStart { .. }));
-    assert!(matches!(events[2], InferenceEvent::ToolCallDelta { .. }));
-    assert!(matches!(events[3], InferenceEvent::ToolCallDelta { .. }));
+    assert!(matches!(
+        events[1],
+        InferenceEvent::ContentBlockStart {
+            index: 0,
+            kind: ContentBlockKind::ToolUse
+        }
+    ));
+    assert!(matches!(
+        events[2],
+        InferenceEvent::ToolCallStart { index: 0, .. }
+    ));
+    assert!(matches!(
+        events[3],
+        InferenceEvent::ToolCallDelta { index: 0, .. }
+    ));
     assert!(matches!(
         events[4],
+        InferenceEvent::ToolCallDelta { index: 0, .. }
+    ));
+    assert!(matches!(
+        events[5],
+        InferenceEvent::ContentBlockStop { index: 0 }
+    ));
+    assert!(matches!(
+        events[6],
         InferenceEvent::MessageEnd {
-            input_tokens: 13,
-            output_tokens: 21,
+            ref usage,
             stop_reason: Some(StopReason::ToolUse)
-        }
+        } if *usage == inference_sdk_core::Usage::new(13, 21)
     ));
 }
 
@@ -85,8 +113,28 @@ fn test_anthropic_provider_contract_maps_provider_error_to_err() {
     });
 
     assert_eq!(events.len(), 1);
-    assert!(matches!(
-        events[0],
-        Err(SdkError::ProviderError(ref msg)) if msg == "boom"
-    ));
+    let Err(SdkError::ApiStatusError(ref err)) = events[0] else {
+        panic!("unexpected event: {:?}", events[0]);
+    };
+    assert_eq!(err.status, 400);
+    assert_eq!(err.error_type.as_deref(), Some("invalid_request_error"));
+    assert_eq!(err.message, "boom");
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn test_anthropic_provider_contract_classifies_in_stream_errors() {
+    for (error_type, rate_limit) in [("rate_limit_error", true), ("overloaded_error", false)] {
+        let mut adapter = AnthropicStreamAdapter::new();
+        let mut events = adapter.process_event(StreamEvent::Error {
+            error: ErrorDetails {
+                error_type: error_type.to_string(),
+                message: "try later".to_string(),
+            },
+        });
+
+        let error = events.pop().unwrap().unwrap_err();
+        assert!(error.is_retryable(), "{error_type}");
+        assert_eq!(error.is_rate_limit(), rate_limit, "{error_type}");
+    }
 }
@@ -0,0 +1,68 @@
+//! Cooperative cancellation for in-flight requests and streams.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::Notify;
+
+/// A cloneable handle that cancels every request and stream it was attached to.
+///
+/// Attach it with [`RequestOptions::with_cancellation`](crate::RequestOptions::with_cancellation).
+/// Cancelling aborts retry waits, in-flight HTTP requests, and open streams with
+/// [`SdkError::Cancelled`](crate::SdkError::Cancelled), dropping the underlying connection.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token. Idempotent; later requests using it fail immediately.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes once the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking the flag so a concurrent `cancel` is not missed.
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+
+    #[tokio::test]
+    async fn test_cancelled_resolves_for_every_clone() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        let waiter = clone.cancelled();
+        token.cancel();
+        waiter.await;
+        assert!(clone.is_cancelled());
+        // Already-cancelled tokens resolve immediately.
+        token.cancelled().await;
+    }
+}
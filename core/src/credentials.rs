@@ -0,0 +1,338 @@
+//! API key sources consulted on every request, so keys can rotate without rebuilding clients.
+
+use crate::env;
+use crate::error::SdkError;
+use futures_util::future::BoxFuture;
+use reqwest::header::{AUTHORIZATION, HeaderName, HeaderValue};
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+const DEFAULT_REFRESH_TTL: Duration = Duration::from_secs(300);
+const DEFAULT_FILE_TTL: Duration = Duration::from_secs(10);
+
+/// Supplies the API key for each HTTP attempt.
+///
+/// Implementations should cache expensive lookups; `Debug` output must not reveal the key.
+pub trait CredentialProvider: fmt::Debug + Send + Sync + 'static {
+    fn api_key(&self) -> BoxFuture<'_, Result<String, SdkError>>;
+}
+
+/// A fixed key, as used by `ClientConfig::new`.
+#[derive(Clone)]
+pub struct StaticCredentials {
+    api_key: String,
+}
+
+impl StaticCredentials {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+        }
+    }
+}
+
+// Manually implement Debug to redact the API key
+impl fmt::Debug for StaticCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticCredentials")
+            .field("api_key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl CredentialProvider for StaticCredentials {
+    fn api_key(&self) -> BoxFuture<'_, Result<String, SdkError>> {
+        Box::pin(async move { Ok(self.api_key.clone()) })
+    }
+}
+
+/// Reads the key from an environment variable on every request.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    var: String,
+}
+
+impl EnvCredentials {
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn api_key(&self) -> BoxFuture<'_, Result<String, SdkError>> {
+        Box::pin(async move { env::required_var(&|name| std::env::var(name), &self.var) })
+    }
+}
+
+/// Reads the key from a file (e.g. a mounted secret). The file is re-read when its modification
+/// time or length changes, and at least once per TTL (ten seconds by default) to catch rotations
+/// within the filesystem's timestamp granularity. Surrounding whitespace is trimmed.
+pub struct FileCredentials {
+    path: PathBuf,
+    ttl: Duration,
+    cached: Mutex<Option<CachedFile>>,
+}
+
+struct CachedFile {
+    modified: SystemTime,
+    len: u64,
+    read_at: Instant,
+    key: String,
+}
+
+impl FileCredentials {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ttl: DEFAULT_FILE_TTL,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    async fn read(&self) -> Result<String, SdkError> {
+        let metadata = tokio::fs::metadata(&self.path)
+            .await
+            .map_err(|e| file_error(&self.path, e))?;
+        let modified = metadata.modified().map_err(|e| file_error(&self.path, e))?;
+        if let Some(cached) = self.lock().as_ref()
+            && cached.modified == modified
+            && cached.len == metadata.len()
+            && cached.read_at.elapsed() < self.ttl
+        {
+            return Ok(cached.key.clone());
+        }
+
+        let key = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| file_error(&self.path, e))?
+            .trim()
+            .to_string();
+        if key.is_empty() {
+            return Err(SdkError::ConfigError(format!(
+                "Credential file {} is empty",
+                self.path.display()
+            )));
+        }
+        *self.lock() = Some(CachedFile {
+            modified,
+            len: metadata.len(),
+            read_at: Instant::now(),
+            key: key.clone(),
+        });
+        Ok(key)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<CachedFile>> {
+        self.cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// Manually implement Debug so the cached key is never printed
+impl fmt::Debug for FileCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileCredentials")
+            .field("path", &self.path)
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Clones start with an empty cache and re-read the file on first use.
+impl Clone for FileCredentials {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone()).with_ttl(self.ttl)
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn api_key(&self) -> BoxFuture<'_, Result<String, SdkError>> {
+        Box::pin(self.read())
+    }
+}
+
+fn file_error(path: &Path, error: std::io::Error) -> SdkError {
+    SdkError::ConfigError(format!(
+        "Failed to read credential file {}: {error}",
+        path.display()
+    ))
+}
+
+type RefreshFn = dyn Fn() -> BoxFuture<'static, Result<String, SdkError>> + Send + Sync;
+
+/// Fetches the key with an async callback (e.g. from a secrets manager) and reuses it for a TTL.
+///
+/// Concurrent requests wait for a single refresh. A failed refresh is returned to the request
+/// that triggered it and retried by the next one.
+pub struct RefreshingCredentials {
+    refresh: Arc<RefreshFn>,
+    ttl: Duration,
+    cached: tokio::sync::Mutex<Option<(Instant, String)>>,
+}
+
+impl RefreshingCredentials {
+    /// Caches each key for five minutes; see [`with_ttl`](Self::with_ttl).
+    pub fn new<F, Fut>(refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, SdkError>> + Send + 'static,
+    {
+        Self {
+            refresh: Arc::new(move || Box::pin(refresh())),
+            ttl: DEFAULT_REFRESH_TTL,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Drops the cached key so the next request refreshes it, e.g. after a 401.
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+}
+
+impl fmt::Debug for RefreshingCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshingCredentials")
+            .field("ttl", &self.ttl)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialProvider for RefreshingCredentials {
+    fn api_key(&self) -> BoxFuture<'_, Result<String, SdkError>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            if let Some((fetched_at, key)) = cached.as_ref()
+                && fetched_at.elapsed() < self.ttl
+            {
+                return Ok(key.clone());
+            }
+            let key = (self.refresh)().await?;
+            *cached = Some((Instant::now(), key.clone()));
+            Ok(key)
+        })
+    }
+}
+
+/// How a provider sends the key: the header name and an optional auth scheme.
+#[derive(Clone, Debug)]
+pub struct AuthHeader {
+    credentials: Arc<dyn CredentialProvider>,
+    name: HeaderName,
+    scheme: Option<&'static str>,
+}
+
+impl AuthHeader {
+    /// Sends the raw key in `name` (e.g. Anthropic's `x-api-key`).
+    pub fn new(credentials: Arc<dyn CredentialProvider>, name: HeaderName) -> Self {
+        Self {
+            credentials,
+            name,
+            scheme: None,
+        }
+    }
+
+    /// Sends `Authorization: Bearer <key>`.
+    pub fn bearer(credentials: Arc<dyn CredentialProvider>) -> Self {
+        Self {
+            credentials,
+            name: AUTHORIZATION,
+            scheme: Some("Bearer"),
+        }
+    }
+
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+
+    /// Fetches the current key and formats it as a sensitive header value.
+    pub async fn value(&self) -> Result<HeaderValue, SdkError> {
+        let key = self.credentials.api_key().await?;
+        let raw = match self.scheme {
+            Some(scheme) => format!("{scheme} {key}"),
+            None => key,
+        };
+        let mut value = HeaderValue::from_str(&raw)
+            .map_err(|e| SdkError::ConfigError(format!("Invalid API key: {}", e)))?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_refreshing_credentials_cache_until_ttl_or_invalidate() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let credentials = RefreshingCredentials::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok(format!("key-{n}")) }
+        });
+
+        assert_eq!(credentials.api_key().await.unwrap(), "key-0");
+        assert_eq!(credentials.api_key().await.unwrap(), "key-0");
+        credentials.invalidate().await;
+        assert_eq!(credentials.api_key().await.unwrap(), "key-1");
+
+        let credentials = credentials.with_ttl(Duration::ZERO);
+        assert_eq!(credentials.api_key().await.unwrap(), "key-2");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_file_credentials_reload_when_file_changes() {
+        let path = std::env::temp_dir().join(format!(
+            "inference-sdk-credentials-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::write(&path, "first-key\n").unwrap();
+        let credentials = FileCredentials::new(&path);
+        assert_eq!(credentials.api_key().await.unwrap(), "first-key");
+        assert!(!format!("{credentials:?}").contains("first-key"));
+
+        // Same timestamp granularity, different length.
+        std::fs::write(&path, "second-key-longer").unwrap();
+        assert_eq!(credentials.api_key().await.unwrap(), "second-key-longer");
+
+        // Same length: only the TTL catches it.
+        std::fs::write(&path, "third--key-longer").unwrap();
+        let credentials = credentials.with_ttl(Duration::ZERO);
+        assert_eq!(credentials.api_key().await.unwrap(), "third--key-longer");
+
+        std::fs::remove_file(&path).unwrap();
+        let err = credentials.api_key().await.unwrap_err();
+        assert!(err.to_string().contains("credential file"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_auth_header_formats_and_redacts() {
+        let credentials: Arc<dyn CredentialProvider> = Arc::new(StaticCredentials::new("sk-1"));
+        let bearer = AuthHeader::bearer(credentials.clone());
+        let value = bearer.value().await.unwrap();
+        assert_eq!(value, "Bearer sk-1");
+        assert!(value.is_sensitive());
+        assert!(!format!("{bearer:?}").contains("sk-1"));
+
+        let raw = AuthHeader::new(credentials, HeaderName::from_static("x-api-key"));
+        assert_eq!(raw.value().await.unwrap(), "sk-1");
+    }
+}
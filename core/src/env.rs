@@ -0,0 +1,81 @@
+//! Reading client settings from environment variables.
+//!
+//! Provider configs take an [`EnvLookup`] so their `from_env` constructors can be tested without
+//! touching the process environment.
+
+use crate::error::SdkError;
+use std::env::VarError;
+
+/// Looks up one variable; `from_env` constructors pass `std::env::var`.
+pub type EnvLookup<'a> = &'a dyn Fn(&str) -> Result<String, VarError>;
+
+/// The value of `name`, or a `ConfigError` naming it when it is unset, empty, or not UTF-8.
+pub fn required_var(lookup: EnvLookup<'_>, name: &str) -> Result<String, SdkError> {
+    optional_var(lookup, name)?
+        .ok_or_else(|| SdkError::ConfigError(format!("Environment variable {name} is not set")))
+}
+
+/// The value of `name`, or `None` when it is unset or empty.
+pub fn optional_var(lookup: EnvLookup<'_>, name: &str) -> Result<Option<String>, SdkError> {
+    match lookup(name) {
+        Ok(value) if value.trim().is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(VarError::NotPresent) => Ok(None),
+        Err(VarError::NotUnicode(_)) => Err(SdkError::ConfigError(format!(
+            "Environment variable {name} is not valid UTF-8"
+        ))),
+    }
+}
+
+/// Prefixes a `ConfigError` raised while applying `name`'s value with the variable name.
+pub fn named_error(name: &str, error: SdkError) -> SdkError {
+    match error {
+        SdkError::ConfigError(message) => SdkError::ConfigError(format!("{name}: {message}")),
+        other => other,
+    }
+}
+
+/// A lookup backed by fixed `(name, value)` pairs, for testing `from_env` constructors.
+#[cfg(any(test, feature = "test-util"))]
+pub fn lookup_from<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Result<String, VarError> + 'a {
+    move |name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.to_string())
+            .ok_or(VarError::NotPresent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsString;
+
+    fn lookup(name: &str) -> Result<String, VarError> {
+        match name {
+            "SET" => Ok("value".to_string()),
+            "EMPTY" => Ok("  ".to_string()),
+            "BINARY" => Err(VarError::NotUnicode(OsString::from("x"))),
+            _ => Err(VarError::NotPresent),
+        }
+    }
+
+    #[test]
+    fn test_required_var_names_the_missing_variable() {
+        assert_eq!(required_var(&lookup, "SET").unwrap(), "value");
+        for name in ["UNSET", "EMPTY", "BINARY"] {
+            let err = required_var(&lookup, name).unwrap_err();
+            assert!(
+                matches!(&err, SdkError::ConfigError(message) if message.contains(name)),
+                "{err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_optional_var_treats_empty_as_unset() {
+        assert_eq!(optional_var(&lookup, "EMPTY").unwrap(), None);
+        assert_eq!(optional_var(&lookup, "UNSET").unwrap(), None);
+        assert!(optional_var(&lookup, "BINARY").is_err());
+    }
+}
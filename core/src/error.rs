@@ -1,3 +1,5 @@
+use crate::rate_limit::RateLimitInfo;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Stream contract violations detected while assembling normalized events.
@@ -9,6 +11,16 @@ pub enum StreamInvariantViolation {
     DuplicateMessageStart,
     #[error("tool_call_delta was emitted before tool_call_start")]
     ToolCallDeltaBeforeStart,
+    #[error("tool_call_start was emitted twice for the same index")]
+    DuplicateToolCallStart,
+    #[error("tool_call_delta was emitted after its content block stopped")]
+    ToolCallDeltaAfterBlockStop,
+    #[error("content_block_start was emitted twice for the same index")]
+    DuplicateContentBlockStart,
+    #[error("content_block_stop was emitted for a block that is not open")]
+    ContentBlockStopWithoutStart,
+    #[error("message_end was emitted while a content block was still open")]
+    UnclosedContentBlock,
     #[error("message_end was emitted before message_start")]
     MessageEndBeforeStart,
     #[error("events were emitted after message_end")]
@@ -17,19 +29,32 @@ pub enum StreamInvariantViolation {
     MissingMessageEnd,
     #[error("stream ended without a message_start")]
     MissingMessageStart,
-    #[error("tool call stream ended without a tool id")]
+    #[error("tool call started without a tool id")]
     ToolCallMissingId,
-    #[error("tool call stream ended without a tool name")]
+    #[error("tool call started without a tool name")]
     ToolCallMissingName,
 }
 
 /// Base error type shared across all provider SDKs.
 #[derive(Error, Debug)]
 pub enum SdkError {
-    #[error("API error: {0}")]
-    ApiError(String),
+    #[error(transparent)]
+    ApiStatusError(Box<ApiStatusError>),
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
+    #[error("Request timed out after {timeout:?}")]
+    RequestTimeout { timeout: Duration },
+    #[error("Total timeout budget of {budget:?} exceeded after {elapsed:?}")]
+    TotalTimeoutExceeded { budget: Duration, elapsed: Duration },
+    #[error("Stream stalled: no event received for {idle:?}")]
+    StreamIdleTimeout { idle: Duration },
+    #[error("Request was cancelled")]
+    Cancelled,
+    #[error("Request failed after {attempts} attempts: {last_error}")]
+    RetriesExhausted {
+        attempts: u32,
+        last_error: Box<SdkError>,
+    },
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
     #[error("Invalid configuration: {0}")]
@@ -40,6 +65,228 @@ pub enum SdkError {
     StreamInvariantViolation(#[from] StreamInvariantViolation),
     #[error("Provider error: {0}")]
     ProviderError(String),
+    #[error("Provider {provider} does not support parameter `{parameter}`")]
+    UnsupportedParameter { provider: String, parameter: String },
+    #[error("Structured output error: {0}")]
+    StructuredOutputError(String),
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
+
+impl SdkError {
+    /// The structured HTTP error, if this error came from a non-2xx response.
+    ///
+    /// Looks through [`SdkError::RetriesExhausted`] to the last attempt's error.
+    pub fn api_status(&self) -> Option<&ApiStatusError> {
+        match self {
+            Self::ApiStatusError(err) => Some(err),
+            Self::RetriesExhausted { last_error, .. } => last_error.api_status(),
+            _ => None,
+        }
+    }
+
+    /// Whether the provider rejected the request for exceeding a rate limit.
+    pub fn is_rate_limit(&self) -> bool {
+        self.api_status().is_some_and(ApiStatusError::is_rate_limit)
+    }
+
+    /// Whether the prompt (plus requested output) did not fit the model's context window.
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.api_status()
+            .is_some_and(ApiStatusError::is_context_length_exceeded)
+    }
+
+    /// Whether retrying the same request later may succeed.
+    ///
+    /// An exhausted total timeout budget is not retryable; exhausted retries are retryable
+    /// when their last error is.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ApiStatusError(err) => err.is_retryable(),
+            Self::NetworkError(err) => err.is_timeout() || err.is_connect(),
+            Self::RequestTimeout { .. } | Self::StreamIdleTimeout { .. } => true,
+            Self::RetriesExhausted { last_error, .. } => last_error.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+const BILLING_ERROR_CODES: &[&str] = &[
+    "insufficient_quota",
+    "billing_hard_limit_reached",
+    "billing_not_active",
+];
+
+/// A non-2xx HTTP response, with the provider's error payload parsed where possible.
+///
+/// Anthropic bodies look like `{"type":"error","error":{"type":..,"message":..}}` and OpenAI
+/// bodies like `{"error":{"type":..,"code":..,"message":..}}`; both land in the same fields.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("API request failed (status {status}): {}", self.summary())]
+pub struct ApiStatusError {
+    /// HTTP status code.
+    pub status: u16,
+    /// Provider error type (`error.type`), e.g. `rate_limit_error` or `invalid_request_error`.
+    pub error_type: Option<String>,
+    /// Provider error code (`error.code`, OpenAI only), e.g. `context_length_exceeded`.
+    pub code: Option<String>,
+    /// Human-readable message (`error.message`), or the raw body when it is not JSON.
+    pub message: String,
+    /// Request id from the `request-id` (Anthropic) or `x-request-id` (OpenAI) header.
+    pub request_id: Option<String>,
+    /// Server-requested delay from the `Retry-After` header.
+    pub retry_after: Option<Duration>,
+    /// The unparsed response body.
+    pub body: String,
+    /// Rate-limit headers of the response, when the provider sent them.
+    pub rate_limit: Option<RateLimitInfo>,
+}
+
+impl ApiStatusError {
+    /// Builds the error from a response status, request id, retry-after, and raw body.
+    pub fn new(
+        status: u16,
+        request_id: Option<String>,
+        retry_after: Option<Duration>,
+        body: String,
+    ) -> Self {
+        let error = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|json| json.get("error").cloned());
+        let field = |name: &str| {
+            error
+                .as_ref()
+                .and_then(|e| e.get(name))
+                .and_then(|value| match value {
+                    serde_json::Value::String(s) => Some(s.clone()),
+                    serde_json::Value::Number(n) => Some(n.to_string()),
+                    _ => None,
+                })
+        };
+
+        Self {
+            status,
+            error_type: field("type"),
+            code: field("code"),
+            message: field("message").unwrap_or_else(|| body.clone()),
+            request_id,
+            retry_after,
+            body,
+            rate_limit: None,
+        }
+    }
+
+    /// A throttled request; quota and billing failures are excluded even though they share
+    /// status `429`.
+    pub fn is_rate_limit(&self) -> bool {
+        !self.is_quota_exhausted()
+            && (self.status == 429
+                || self.error_type.as_deref() == Some("rate_limit_error")
+                || self.code.as_deref() == Some("rate_limit_exceeded"))
+    }
+
+    /// Whether the account is out of quota or has a billing problem (OpenAI
+    /// `insufficient_quota`, `billing_hard_limit_reached`, `billing_not_active`). Retrying
+    /// cannot succeed until the account is fixed.
+    pub fn is_quota_exhausted(&self) -> bool {
+        [self.code.as_deref(), self.error_type.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|kind| BILLING_ERROR_CODES.contains(&kind))
+    }
+
+    pub fn is_context_length_exceeded(&self) -> bool {
+        if self.code.as_deref() == Some("context_length_exceeded") {
+            return true;
+        }
+        // Anthropic has no dedicated code; it reports `invalid_request_error` with this wording.
+        self.error_type.as_deref() == Some("invalid_request_error")
+            && self.message.contains("prompt is too long")
+    }
+
+    /// Timeouts, rate limits, overload (`529`), and server errors are retryable, matching the
+    /// statuses the default `RetryPolicy` retries; quota and billing failures are not.
+    pub fn is_retryable(&self) -> bool {
+        !self.is_quota_exhausted() && (matches!(self.status, 408 | 429) || self.status >= 500)
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = match self.error_type.as_deref().or(self.code.as_deref()) {
+            Some(kind) => format!("{kind}: {}", self.message),
+            None => self.message.clone(),
+        };
+        if let Some(request_id) = &self.request_id {
+            summary.push_str(&format!(" (request id {request_id})"));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApiStatusError, SdkError};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parses_anthropic_error_body() {
+        let err = ApiStatusError::new(
+            429,
+            Some("req_123".to_string()),
+            Some(Duration::from_secs(3)),
+            r#"{"type":"error","error":{"type":"rate_limit_error","message":"slow down"}}"#
+                .to_string(),
+        );
+        assert_eq!(err.error_type.as_deref(), Some("rate_limit_error"));
+        assert_eq!(err.message, "slow down");
+        assert!(err.is_rate_limit());
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "API request failed (status 429): rate_limit_error: slow down (request id req_123)"
+        );
+    }
+
+    #[test]
+    fn test_parses_openai_error_body() {
+        let err = SdkError::ApiStatusError(Box::new(ApiStatusError::new(
+            400,
+            None,
+            None,
+            r#"{"error":{"message":"too long","type":"invalid_request_error","param":"messages","code":"context_length_exceeded"}}"#
+                .to_string(),
+        )));
+        assert!(err.is_context_length_exceeded());
+        assert!(!err.is_rate_limit());
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.api_status().and_then(|e| e.code.as_deref()),
+            Some("context_length_exceeded")
+        );
+    }
+
+    #[test]
+    fn test_insufficient_quota_is_neither_rate_limit_nor_retryable() {
+        let err = SdkError::ApiStatusError(Box::new(ApiStatusError::new(
+            429,
+            None,
+            None,
+            r#"{"error":{"message":"You exceeded your current quota","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#
+                .to_string(),
+        )));
+        assert!(err.api_status().unwrap().is_quota_exhausted());
+        assert!(!err.is_rate_limit());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_falls_back_to_raw_body() {
+        let err = ApiStatusError::new(500, None, None, "Internal Server Error".to_string());
+        assert_eq!(err.error_type, None);
+        assert_eq!(err.message, "Internal Server Error");
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "API request failed (status 500): Internal Server Error"
+        );
+    }
+}
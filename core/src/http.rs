@@ -1,12 +1,34 @@
-use crate::error::SdkError;
+use crate::cancellation::CancellationToken;
+use crate::credentials::AuthHeader;
+use crate::error::{ApiStatusError, SdkError};
+use crate::metrics::StreamMetrics;
+use crate::middleware::MiddlewareStack;
+use crate::rate_limit::{RateLimitInfo, RateLimiter};
+use crate::telemetry;
+use futures_core::Stream;
+use futures_util::StreamExt;
+use futures_util::future::{BoxFuture, Either};
 use reqwest::Method;
 use reqwest::StatusCode;
-use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue, RETRY_AFTER};
 use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tracing::warn;
+use tracing::field::Empty;
+use tracing::{Instrument, Span, warn};
+
+pub mod transport;
+
+pub use transport::{
+    CannedResponse, HttpTransport, InMemoryTransport, NetworkConfig, RecordedRequest,
+    ReqwestTransport,
+};
 
 const MAX_RETRIES_CAP: u32 = 10;
+const RETRY_AFTER_MS: &str = "retry-after-ms";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RetryStatusRule {
@@ -29,6 +51,15 @@ pub struct RetryPolicy {
     pub jitter: Duration,
     pub retryable_statuses: Vec<RetryStatusRule>,
     pub retryable_network_errors: Vec<RetryNetworkRule>,
+    /// Re-issue a streaming request whose stream fails before its first content event.
+    ///
+    /// Off by default. Stream retries count against `max_retries` separately from the
+    /// HTTP-level retries of each attempt, and share the total timeout budget.
+    pub retry_stream_before_first_event: bool,
+    /// Honour a server-requested delay (`Retry-After`/`retry-after-ms`) longer than `max_delay`
+    /// when it still fits in the total timeout budget. Off by default; without a total
+    /// timeout the delay is always capped at `max_delay`.
+    pub allow_server_delay_above_max: bool,
 }
 
 impl Default for RetryPolicy {
@@ -48,6 +79,8 @@ impl Default for RetryPolicy {
                 RetryNetworkRule::Connect,
                 RetryNetworkRule::Request,
             ],
+            retry_stream_before_first_event: false,
+            allow_server_delay_above_max: false,
         }
     }
 }
@@ -82,12 +115,24 @@ impl RetryPolicy {
         self.retryable_network_errors = errors;
         self
     }
+
+    pub fn with_stream_retry(mut self, enabled: bool) -> Self {
+        self.retry_stream_before_first_event = enabled;
+        self
+    }
+
+    pub fn with_server_delay_above_max(mut self, enabled: bool) -> Self {
+        self.allow_server_delay_above_max = enabled;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct TimeoutPolicy {
     pub request_timeout: Option<Duration>,
     pub total_timeout: Option<Duration>,
+    /// Longest gap allowed between two events of a streaming response (including the first).
+    pub idle_timeout: Option<Duration>,
 }
 
 impl TimeoutPolicy {
@@ -100,6 +145,11 @@ impl TimeoutPolicy {
         self.total_timeout = Some(timeout);
         self
     }
+
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -109,6 +159,8 @@ pub struct RequestOptions {
     pub max_retries: Option<u32>,
     pub retry_policy: Option<RetryPolicy>,
     pub timeout_policy: Option<TimeoutPolicy>,
+    pub cancellation: Option<CancellationToken>,
+    pub metrics: Option<StreamMetrics>,
 }
 
 impl RequestOptions {
@@ -161,6 +213,18 @@ impl RequestOptions {
         self.timeout_policy = Some(policy);
         self
     }
+
+    /// Abort the request (including retry waits and an open stream) when `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Record timings, retries, and received bytes of this call into `metrics`.
+    pub fn with_metrics(mut self, metrics: StreamMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
 }
 
 /// Retry configuration extracted from a client's defaults and per-request options.
@@ -168,8 +232,17 @@ impl RequestOptions {
 pub struct RetryConfig {
     pub base_url: String,
     pub endpoint: String,
+    /// Headers sent with every attempt (e.g. the API version); per-request headers override them.
+    pub default_headers: HeaderMap,
+    /// Credential header resolved before every attempt; a per-request header of the same name
+    /// takes precedence.
+    pub auth: Option<AuthHeader>,
     pub retry_policy: RetryPolicy,
     pub timeout_policy: TimeoutPolicy,
+    /// Shared limiter consulted before every attempt and fed every response's rate-limit headers.
+    pub rate_limiter: Option<RateLimiter>,
+    /// Middleware every attempt passes through.
+    pub middleware: MiddlewareStack,
 }
 
 fn should_retry_status(status: StatusCode, retry_policy: &RetryPolicy) -> bool {
@@ -218,10 +291,49 @@ fn random_jitter(max_jitter_ms: u64) -> u64 {
     seed % max_jitter_ms
 }
 
-fn retry_after_delay(headers: &HeaderMap, retry_policy: &RetryPolicy) -> Option<Duration> {
-    let header = headers.get(RETRY_AFTER)?;
-    let seconds = header.to_str().ok()?.trim().parse::<u64>().ok()?;
-    Some(Duration::from_secs(seconds).min(retry_policy.max_delay))
+/// The server-requested delay: `retry-after-ms`, else `Retry-After` as seconds or an HTTP-date
+/// (relative to `now`; dates in the past mean no delay).
+fn retry_after(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let header = |name| headers.get(name)?.to_str().ok().map(str::trim);
+
+    if let Some(millis) = header(RETRY_AFTER_MS).and_then(|v| v.parse::<f64>().ok())
+        && let Ok(delay) = Duration::try_from_secs_f64(millis / 1000.0)
+    {
+        return Some(delay);
+    }
+
+    let value = header(RETRY_AFTER.as_str())?;
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(now).unwrap_or_default())
+}
+
+/// How long to wait before the next attempt as requested by the server, capped at `max_delay`
+/// unless the policy allows longer waits that fit in the remaining total budget.
+fn retry_after_delay(
+    headers: &HeaderMap,
+    retry_policy: &RetryPolicy,
+    timeout_policy: &TimeoutPolicy,
+    elapsed: Duration,
+    now: SystemTime,
+) -> Option<Duration> {
+    let delay = retry_after(headers, now)?;
+    if retry_policy.allow_server_delay_above_max
+        && let Some(total_timeout) = timeout_policy.total_timeout
+        && elapsed.saturating_add(delay) <= total_timeout
+    {
+        return Some(delay);
+    }
+    Some(delay.min(retry_policy.max_delay))
+}
+
+/// Anthropic sends `request-id`; OpenAI sends `x-request-id`.
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    ["request-id", "x-request-id"]
+        .into_iter()
+        .find_map(|name| headers.get(name)?.to_str().ok().map(str::to_string))
 }
 
 fn resolve_retry_policy(config: &RetryConfig, options: &RequestOptions) -> RetryPolicy {
@@ -257,66 +369,177 @@ fn exceeds_total_budget(started_at: Instant, total_timeout: Duration, next_wait:
     started_at.elapsed().saturating_add(next_wait) > total_timeout
 }
 
+/// Runs `future` to completion unless the request's cancellation token fires first.
+pub async fn cancellable<F: Future>(
+    options: &RequestOptions,
+    future: F,
+) -> Result<F::Output, SdkError> {
+    let Some(token) = &options.cancellation else {
+        return Ok(future.await);
+    };
+    if token.is_cancelled() {
+        return Err(SdkError::Cancelled);
+    }
+    match futures_util::future::select(std::pin::pin!(future), std::pin::pin!(token.cancelled()))
+        .await
+    {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(((), _)) => Err(SdkError::Cancelled),
+    }
+}
+
+/// The span for one HTTP attempt, following the OpenTelemetry HTTP client conventions.
+fn attempt_span(url: &str, resend_count: u32) -> Span {
+    tracing::info_span!(
+        "http.attempt",
+        otel.name = "POST",
+        otel.kind = "client",
+        http.request.method = "POST",
+        url.full = url,
+        http.request.resend_count = resend_count,
+        http.response.status_code = Empty,
+        error.type = Empty,
+    )
+}
+
+/// Wraps the last attempt's error in `RetriesExhausted` when it was retried and still failed
+/// with a retryable error; non-retryable failures and single attempts are returned as-is.
+fn final_error(error: SdkError, retryable: bool, retries: u32) -> SdkError {
+    if retryable && retries > 0 {
+        SdkError::RetriesExhausted {
+            attempts: retries + 1,
+            last_error: Box::new(error),
+        }
+    } else {
+        error
+    }
+}
+
+/// The client's default headers overlaid with the per-request ones, plus the JSON content type.
+fn request_headers(config: &RetryConfig, options: &RequestOptions) -> HeaderMap {
+    let mut headers = config.default_headers.clone();
+    for name in options.headers.keys() {
+        headers.remove(name);
+    }
+    for (name, value) in &options.headers {
+        headers.append(name, value.clone());
+    }
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers
+}
+
 /// Send an HTTP POST request with exponential backoff retry.
 ///
 /// This is the shared "Physics" layer: every provider SDK uses this
 /// to send requests and handle transient failures identically.
 pub async fn send_with_retry<T: Serialize>(
-    http_client: &reqwest::Client,
+    transport: &dyn HttpTransport,
     config: &RetryConfig,
     request_body: &T,
     options: &RequestOptions,
 ) -> Result<reqwest::Response, SdkError> {
     let url = format!("{}{}", config.base_url, config.endpoint);
+    let parsed_url = reqwest::Url::parse(&url)
+        .map_err(|e| SdkError::ConfigError(format!("Invalid request URL {url}: {e}")))?;
+    let body = serde_json::to_vec(request_body)?;
     let retry_policy = resolve_retry_policy(config, options);
     let timeout_policy = resolve_timeout_policy(config, options);
     let max_retries = retry_policy.max_retries;
     let started_at = Instant::now();
     let mut retries = 0;
+    if let Some(metrics) = &options.metrics {
+        metrics.start();
+    }
 
     loop {
         if let Some(total_timeout) = timeout_policy.total_timeout
             && started_at.elapsed() > total_timeout
         {
-            return Err(SdkError::ApiError(format!(
-                "API request aborted: total timeout budget of {:?} was exceeded",
-                total_timeout
-            )));
+            return Err(SdkError::TotalTimeoutExceeded {
+                budget: total_timeout,
+                elapsed: started_at.elapsed(),
+            });
         }
 
-        let mut request_builder = http_client.request(Method::POST, &url).json(request_body);
-
-        if let Some(timeout) = timeout_policy.request_timeout {
-            request_builder = request_builder.timeout(timeout);
+        if let Some(limiter) = &config.rate_limiter {
+            cancellable(options, limiter.acquire()).await?;
         }
 
-        if !options.headers.is_empty() {
-            request_builder = request_builder.headers(options.headers.clone());
+        let mut request = reqwest::Request::new(Method::POST, parsed_url.clone());
+        let mut headers = request_headers(config, options);
+        if let Some(auth) = &config.auth
+            && !options.headers.contains_key(auth.name())
+        {
+            headers.insert(
+                auth.name().clone(),
+                cancellable(options, auth.value()).await??,
+            );
+        }
+        *request.headers_mut() = headers;
+        *request.body_mut() = Some(body.clone().into());
+        *request.timeout_mut() = timeout_policy.request_timeout;
+        Span::current().record("retry_count", retries);
+        let attempt_span = attempt_span(&url, retries);
+        let response_result = cancellable(
+            options,
+            config
+                .middleware
+                .run(transport, request)
+                .instrument(attempt_span.clone()),
+        )
+        .await?;
+        match &response_result {
+            Ok(response) => {
+                attempt_span.record("http.response.status_code", response.status().as_u16());
+                if !response.status().is_success() {
+                    attempt_span.record("error.type", response.status().as_str());
+                }
+            }
+            Err(error) => {
+                attempt_span.record("error.type", telemetry::error_type(error));
+            }
         }
-
-        let response_result = request_builder.send().await;
 
         match response_result {
             Ok(response) => {
+                let rate_limit = RateLimitInfo::from_headers(response.headers());
+                if let (Some(limiter), Some(info)) = (&config.rate_limiter, &rate_limit) {
+                    limiter.observe(info);
+                }
                 if response.status().is_success() {
                     return Ok(response);
                 }
 
                 let status = response.status();
-                if should_retry_status(status, &retry_policy) && retries < max_retries {
+                let server_delay = retry_after_delay(
+                    response.headers(),
+                    &retry_policy,
+                    &timeout_policy,
+                    started_at.elapsed(),
+                    SystemTime::now(),
+                );
+                let request_id = request_id(response.headers());
+                let retry_after = retry_after(response.headers(), SystemTime::now());
+                let body = cancellable(options, response.text())
+                    .await?
+                    .unwrap_or_default();
+                let mut error = ApiStatusError::new(status.as_u16(), request_id, retry_after, body);
+                error.rate_limit = rate_limit;
+                // Quota and billing failures share 429 with rate limits but never recover.
+                let retryable =
+                    should_retry_status(status, &retry_policy) && !error.is_quota_exhausted();
+                if retryable && retries < max_retries {
                     retries += 1;
-                    let wait = retry_after_delay(response.headers(), &retry_policy)
-                        .unwrap_or_else(|| retry_delay(retries, &retry_policy));
-                    let from_retry_after =
-                        retry_after_delay(response.headers(), &retry_policy).is_some();
+                    let from_retry_after = server_delay.is_some();
+                    let wait = server_delay.unwrap_or_else(|| retry_delay(retries, &retry_policy));
 
                     if let Some(total_timeout) = timeout_policy.total_timeout
                         && exceeds_total_budget(started_at, total_timeout, wait)
                     {
-                        return Err(SdkError::ApiError(format!(
-                            "API request aborted: waiting {:?} would exceed total timeout budget {:?}",
-                            wait, total_timeout
-                        )));
+                        return Err(SdkError::TotalTimeoutExceeded {
+                            budget: total_timeout,
+                            elapsed: started_at.elapsed(),
+                        });
                     }
 
                     warn!(
@@ -328,28 +551,29 @@ pub async fn send_with_retry<T: Serialize>(
                         %url,
                         "retrying request after retryable status"
                     );
-                    tokio::time::sleep(wait).await;
+                    if let Some(metrics) = &options.metrics {
+                        metrics.record_retry();
+                    }
+                    cancellable(options, tokio::time::sleep(wait)).await?;
                     continue;
                 }
 
-                let error_text = response.text().await.unwrap_or_default();
-                return Err(SdkError::ApiError(format!(
-                    "API request failed (status {}): {}",
-                    status, error_text
-                )));
+                let error = SdkError::ApiStatusError(Box::new(error));
+                return Err(final_error(error, retryable, retries));
             }
-            Err(e) => {
-                if should_retry_network_error(&e, &retry_policy) && retries < max_retries {
+            Err(SdkError::NetworkError(e)) => {
+                let retryable = should_retry_network_error(&e, &retry_policy);
+                if retryable && retries < max_retries {
                     retries += 1;
                     let wait = retry_delay(retries, &retry_policy);
 
                     if let Some(total_timeout) = timeout_policy.total_timeout
                         && exceeds_total_budget(started_at, total_timeout, wait)
                     {
-                        return Err(SdkError::ApiError(format!(
-                            "API request aborted: waiting {:?} would exceed total timeout budget {:?}",
-                            wait, total_timeout
-                        )));
+                        return Err(SdkError::TotalTimeoutExceeded {
+                            budget: total_timeout,
+                            elapsed: started_at.elapsed(),
+                        });
                     }
 
                     warn!(
@@ -362,10 +586,260 @@ pub async fn send_with_retry<T: Serialize>(
                         %url,
                         "retrying request after network error"
                     );
-                    tokio::time::sleep(wait).await;
+                    if let Some(metrics) = &options.metrics {
+                        metrics.record_retry();
+                    }
+                    cancellable(options, tokio::time::sleep(wait)).await?;
                     continue;
                 }
-                return Err(SdkError::NetworkError(e));
+                let error = match timeout_policy.request_timeout {
+                    Some(timeout) if e.is_timeout() => SdkError::RequestTimeout { timeout },
+                    _ => SdkError::NetworkError(e),
+                };
+                return Err(final_error(error, retryable, retries));
+            }
+            // Middleware failures are returned as-is.
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Reads and deserializes a non-streaming JSON response body.
+///
+/// The body size and arrival time are recorded into the request's `StreamMetrics`; for these
+/// calls the whole response counts as the first token.
+pub async fn read_json<T: DeserializeOwned>(
+    response: reqwest::Response,
+    options: &RequestOptions,
+) -> Result<T, SdkError> {
+    let body = cancellable(options, response.bytes()).await??;
+    if let Some(metrics) = &options.metrics {
+        metrics.record_response(body.len());
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Counts the body chunks of a streaming response into the request's `StreamMetrics`.
+pub fn metered_bytes<S, B, E>(
+    stream: S,
+    options: &RequestOptions,
+) -> impl Stream<Item = Result<B, E>> + use<S, B, E>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+{
+    let metrics = options.metrics.clone();
+    stream.inspect(move |chunk| {
+        if let (Some(metrics), Ok(bytes)) = (&metrics, chunk) {
+            metrics.record_bytes(bytes.as_ref().len());
+        }
+    })
+}
+
+/// Ends `stream` with [`SdkError::Cancelled`] as soon as the request's cancellation token fires,
+/// dropping the underlying connection.
+pub fn cancellable_stream<T: Send + 'static>(
+    stream: EventStream<T>,
+    options: &RequestOptions,
+) -> EventStream<T> {
+    let Some(token) = options.cancellation.clone() else {
+        return stream;
+    };
+    Box::pin(futures_util::stream::unfold(
+        Some((stream, token)),
+        |state| async move {
+            let (mut stream, token) = state?;
+            if token.is_cancelled() {
+                return Some((Err(SdkError::Cancelled), None));
+            }
+            let next = {
+                let cancelled = std::pin::pin!(token.cancelled());
+                match futures_util::future::select(stream.next(), cancelled).await {
+                    Either::Left((item, _)) => Some(item),
+                    Either::Right(((), _)) => None,
+                }
+            };
+            match next {
+                Some(Some(item)) => Some((item, Some((stream, token)))),
+                Some(None) => None,
+                None => Some((Err(SdkError::Cancelled), None)),
+            }
+        },
+    ))
+}
+
+/// Ends `stream` with [`SdkError::StreamIdleTimeout`] when no event arrives within the
+/// resolved `TimeoutPolicy::idle_timeout`.
+pub fn idle_timeout_stream<T: Send + 'static>(
+    stream: EventStream<T>,
+    config: &RetryConfig,
+    options: &RequestOptions,
+) -> EventStream<T> {
+    let Some(idle) = resolve_timeout_policy(config, options).idle_timeout else {
+        return stream;
+    };
+    Box::pin(futures_util::stream::unfold(
+        Some(stream),
+        move |state| async move {
+            let mut stream = state?;
+            match tokio::time::timeout(idle, stream.next()).await {
+                Ok(Some(item)) => Some((item, Some(stream))),
+                Ok(None) => None,
+                Err(_) => Some((Err(SdkError::StreamIdleTimeout { idle }), None)),
+            }
+        },
+    ))
+}
+
+/// A boxed stream of raw provider events, as returned by the SDKs' streaming resources.
+pub type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T, SdkError>> + Send + 'static>>;
+
+/// How an item of a raw provider stream affects [`retry_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamItemClass {
+    /// Metadata before any content (e.g. `message_start`, pings); held back until content arrives.
+    Preamble,
+    /// Content or any other terminal item; once seen, the stream is never re-issued.
+    Content,
+    /// A transient failure; re-issues the request if no content has been yielded yet.
+    Retryable,
+}
+
+/// Wraps a streaming response so that a failure before the first content event re-issues the
+/// request with the usual backoff, when `RetryPolicy::retry_stream_before_first_event` is set.
+///
+/// `started_at` is when the first attempt was sent; re-issued attempts get what is left of the
+/// total timeout budget. Preamble items of failed attempts are discarded, so the caller sees
+/// exactly one attempt's events.
+pub fn retry_stream<T, F>(
+    config: &RetryConfig,
+    options: &RequestOptions,
+    started_at: Instant,
+    stream: EventStream<T>,
+    classify: fn(&Result<T, SdkError>) -> StreamItemClass,
+    reopen: F,
+) -> EventStream<T>
+where
+    T: Send + 'static,
+    F: FnMut(RequestOptions) -> BoxFuture<'static, Result<EventStream<T>, SdkError>>
+        + Send
+        + 'static,
+{
+    let retry_policy = resolve_retry_policy(config, options);
+    if !retry_policy.retry_stream_before_first_event || retry_policy.max_retries == 0 {
+        return stream;
+    }
+
+    let state = StreamRetryState {
+        stream: Some(stream),
+        buffer: VecDeque::new(),
+        committed: false,
+        retries: 0,
+        retry_policy,
+        timeout_policy: resolve_timeout_policy(config, options),
+        options: options.clone(),
+        started_at,
+        classify,
+        reopen,
+    };
+    Box::pin(futures_util::stream::unfold(
+        state,
+        |mut state| async move {
+            let item = state.next_item().await?;
+            Some((item, state))
+        },
+    ))
+}
+
+struct StreamRetryState<T, F> {
+    stream: Option<EventStream<T>>,
+    buffer: VecDeque<Result<T, SdkError>>,
+    committed: bool,
+    retries: u32,
+    retry_policy: RetryPolicy,
+    timeout_policy: TimeoutPolicy,
+    options: RequestOptions,
+    started_at: Instant,
+    classify: fn(&Result<T, SdkError>) -> StreamItemClass,
+    reopen: F,
+}
+
+impl<T, F> StreamRetryState<T, F>
+where
+    F: FnMut(RequestOptions) -> BoxFuture<'static, Result<EventStream<T>, SdkError>>,
+{
+    async fn next_item(&mut self) -> Option<Result<T, SdkError>> {
+        loop {
+            if self.committed {
+                if let Some(item) = self.buffer.pop_front() {
+                    return Some(item);
+                }
+                return self.stream.as_mut()?.next().await;
+            }
+
+            let Some(item) = self.stream.as_mut()?.next().await else {
+                // Ended without content: release the preamble as-is.
+                self.committed = true;
+                continue;
+            };
+
+            match (self.classify)(&item) {
+                StreamItemClass::Preamble => self.buffer.push_back(item),
+                StreamItemClass::Content => {
+                    self.committed = true;
+                    self.buffer.push_back(item);
+                }
+                StreamItemClass::Retryable => {
+                    if self.retries >= self.retry_policy.max_retries {
+                        self.committed = true;
+                        self.buffer
+                            .push_back(item.map_err(|e| final_error(e, true, self.retries)));
+                        continue;
+                    }
+
+                    self.retries += 1;
+                    let wait = retry_delay(self.retries, &self.retry_policy);
+                    let mut options = self.options.clone();
+                    if let Some(total_timeout) = self.timeout_policy.total_timeout {
+                        if exceeds_total_budget(self.started_at, total_timeout, wait) {
+                            self.committed = true;
+                            self.stream = None;
+                            return Some(Err(SdkError::TotalTimeoutExceeded {
+                                budget: total_timeout,
+                                elapsed: self.started_at.elapsed(),
+                            }));
+                        }
+                        let remaining = total_timeout
+                            .saturating_sub(self.started_at.elapsed().saturating_add(wait));
+                        options.timeout_policy = Some(TimeoutPolicy {
+                            total_timeout: Some(remaining),
+                            ..self.timeout_policy.clone()
+                        });
+                    }
+
+                    warn!(
+                        attempt = self.retries,
+                        max_retries = self.retry_policy.max_retries,
+                        wait_ms = wait.as_millis() as u64,
+                        "retrying stream that failed before its first content event"
+                    );
+                    if let Some(metrics) = &self.options.metrics {
+                        metrics.record_retry();
+                    }
+                    tokio::time::sleep(wait).await;
+                    self.buffer.clear();
+                    let reopened = (self.reopen)(options).await;
+                    // The re-issued request restarts `send_with_retry`'s count on the same span.
+                    Span::current().record("retry_count", self.retries);
+                    match reopened {
+                        Ok(stream) => self.stream = Some(stream),
+                        Err(err) => {
+                            self.committed = true;
+                            self.stream = None;
+                            return Some(Err(err));
+                        }
+                    }
+                }
             }
         }
     }
@@ -375,6 +849,19 @@ pub async fn send_with_retry<T: Serialize>(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_retry_policy_matches_api_status_is_retryable() {
+        let policy = RetryPolicy::default();
+        for status in 400..600 {
+            let error = crate::error::ApiStatusError::new(status, None, None, String::new());
+            assert_eq!(
+                should_retry_status(StatusCode::from_u16(status).unwrap(), &policy),
+                error.is_retryable(),
+                "status {status}"
+            );
+        }
+    }
+
     #[test]
     fn test_default_retry_policy_includes_core_rules() {
         let policy = RetryPolicy::default();
@@ -402,16 +889,267 @@ mod tests {
         assert_eq!(wait, Duration::from_millis(400));
     }
 
+    /// Fixed "now" for Retry-After tests: 2015-10-21 07:28:00 UTC.
+    fn mock_now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_445_412_480)
+    }
+
+    fn retry_after_headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().expect("valid header value"));
+        }
+        headers
+    }
+
     #[test]
     fn test_retry_after_header_is_capped() {
-        let mut headers = HeaderMap::new();
-        headers.insert(RETRY_AFTER, "100".parse().expect("valid retry-after"));
+        let headers = retry_after_headers(&[("retry-after", "100")]);
 
         let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(5));
-        let delay = retry_after_delay(&headers, &policy).expect("retry-after should parse");
+        let delay = retry_after_delay(
+            &headers,
+            &policy,
+            &TimeoutPolicy::default(),
+            Duration::ZERO,
+            mock_now(),
+        )
+        .expect("retry-after should parse");
         assert_eq!(delay, Duration::from_secs(5));
     }
 
+    #[test]
+    fn test_retry_after_parses_http_date_and_milliseconds() {
+        let date = retry_after_headers(&[("retry-after", "Wed, 21 Oct 2015 07:28:30 GMT")]);
+        assert_eq!(
+            retry_after(&date, mock_now()),
+            Some(Duration::from_secs(30))
+        );
+
+        let past = retry_after_headers(&[("retry-after", "Wed, 21 Oct 2015 07:00:00 GMT")]);
+        assert_eq!(retry_after(&past, mock_now()), Some(Duration::ZERO));
+
+        let both = retry_after_headers(&[("retry-after", "3"), ("retry-after-ms", "1500.5")]);
+        assert_eq!(
+            retry_after(&both, mock_now()),
+            Some(Duration::from_micros(1_500_500))
+        );
+
+        let invalid = retry_after_headers(&[("retry-after", "soon")]);
+        assert_eq!(retry_after(&invalid, mock_now()), None);
+    }
+
+    #[test]
+    fn test_server_delay_above_max_only_within_total_budget() {
+        let headers = retry_after_headers(&[("retry-after", "Wed, 21 Oct 2015 07:28:20 GMT")]);
+        let policy = RetryPolicy::default()
+            .with_max_delay(Duration::from_secs(5))
+            .with_server_delay_above_max(true);
+        let budget = TimeoutPolicy::default().with_total_timeout(Duration::from_secs(60));
+        let delay = |timeout_policy: &TimeoutPolicy, elapsed| {
+            retry_after_delay(&headers, &policy, timeout_policy, elapsed, mock_now())
+        };
+
+        assert_eq!(
+            delay(&budget, Duration::from_secs(10)),
+            Some(Duration::from_secs(20))
+        );
+        // Would overrun the budget, or there is no budget: fall back to the cap.
+        assert_eq!(
+            delay(&budget, Duration::from_secs(45)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            delay(&TimeoutPolicy::default(), Duration::ZERO),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_final_error_wraps_only_retried_retryable_failures() {
+        let api_error = || {
+            SdkError::ApiStatusError(Box::new(ApiStatusError::new(
+                503,
+                None,
+                None,
+                String::new(),
+            )))
+        };
+
+        assert!(matches!(
+            final_error(api_error(), true, 2),
+            SdkError::RetriesExhausted { attempts: 3, .. }
+        ));
+        assert!(matches!(
+            final_error(api_error(), true, 0),
+            SdkError::ApiStatusError(_)
+        ));
+        assert!(matches!(
+            final_error(api_error(), false, 2),
+            SdkError::ApiStatusError(_)
+        ));
+    }
+
+    fn stream_config(policy: RetryPolicy) -> RetryConfig {
+        RetryConfig {
+            base_url: String::new(),
+            endpoint: String::new(),
+            default_headers: HeaderMap::new(),
+            auth: None,
+            retry_policy: policy
+                .with_base_delay(Duration::ZERO)
+                .with_jitter(Duration::ZERO),
+            timeout_policy: TimeoutPolicy::default(),
+            rate_limiter: None,
+            middleware: MiddlewareStack::default(),
+        }
+    }
+
+    /// `0` is preamble, positive numbers are content, and errors are retryable.
+    fn classify(item: &Result<i32, SdkError>) -> StreamItemClass {
+        match item {
+            Ok(0) => StreamItemClass::Preamble,
+            Ok(_) => StreamItemClass::Content,
+            Err(_) => StreamItemClass::Retryable,
+        }
+    }
+
+    fn items(items: Vec<Result<i32, SdkError>>) -> EventStream<i32> {
+        Box::pin(futures_util::stream::iter(items))
+    }
+
+    fn reset() -> Result<i32, SdkError> {
+        Err(SdkError::StreamError("connection reset".to_string()))
+    }
+
+    async fn collect_stream(
+        policy: RetryPolicy,
+        first: Vec<Result<i32, SdkError>>,
+        retries: Vec<Vec<Result<i32, SdkError>>>,
+    ) -> (Vec<Result<i32, SdkError>>, usize) {
+        let reopened = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = reopened.clone();
+        let mut retries = VecDeque::from(retries);
+        let stream = retry_stream(
+            &stream_config(policy),
+            &RequestOptions::default(),
+            Instant::now(),
+            items(first),
+            classify,
+            move |_options| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let next = retries.pop_front().unwrap_or_default();
+                Box::pin(async move { Ok(items(next)) })
+            },
+        );
+        let out = stream.collect::<Vec<_>>().await;
+        (out, reopened.load(std::sync::atomic::Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry_reissues_before_first_content() {
+        let policy = RetryPolicy::default().with_stream_retry(true);
+        let (out, reopened) = collect_stream(
+            policy,
+            vec![Ok(0), reset()],
+            vec![vec![Ok(0), Ok(1), Ok(2)]],
+        )
+        .await;
+
+        assert_eq!(reopened, 1);
+        let values: Vec<i32> = out.into_iter().map(|item| item.unwrap()).collect();
+        assert_eq!(values, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry_never_reissues_after_content() {
+        let policy = RetryPolicy::default().with_stream_retry(true);
+        let (out, reopened) =
+            collect_stream(policy, vec![Ok(0), Ok(1), reset()], vec![vec![Ok(9)]]).await;
+
+        assert_eq!(reopened, 0);
+        assert!(matches!(
+            out[..],
+            [Ok(0), Ok(1), Err(SdkError::StreamError(_))]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry_is_opt_in() {
+        let (out, reopened) =
+            collect_stream(RetryPolicy::default(), vec![reset()], vec![vec![Ok(1)]]).await;
+
+        assert_eq!(reopened, 0);
+        assert!(matches!(out[..], [Err(SdkError::StreamError(_))]));
+    }
+
+    #[tokio::test]
+    async fn test_stream_retry_reports_exhaustion() {
+        let policy = RetryPolicy::default()
+            .with_max_retries(1)
+            .with_stream_retry(true);
+        let (out, reopened) = collect_stream(policy, vec![reset()], vec![vec![reset()]]).await;
+
+        assert_eq!(reopened, 1);
+        assert!(matches!(
+            out[..],
+            [Err(SdkError::RetriesExhausted { attempts: 2, .. })]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stream_ends_with_cancelled() {
+        let token = CancellationToken::new();
+        let options = RequestOptions::default().with_cancellation(token.clone());
+        let pending: EventStream<i32> = Box::pin(
+            futures_util::stream::iter(vec![Ok(1)]).chain(futures_util::stream::pending()),
+        );
+        let mut stream = cancellable_stream(pending, &options);
+
+        assert!(matches!(stream.next().await, Some(Ok(1))));
+        token.cancel();
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(SdkError::Cancelled))
+        ));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_fails_fast_for_cancelled_token() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let options = RequestOptions::default().with_cancellation(token);
+        let result = cancellable(&options, futures_util::future::pending::<()>()).await;
+        assert!(matches!(result, Err(SdkError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_stream_reports_stall() {
+        let config = RetryConfig {
+            base_url: String::new(),
+            endpoint: String::new(),
+            default_headers: HeaderMap::new(),
+            auth: None,
+            retry_policy: RetryPolicy::default(),
+            timeout_policy: TimeoutPolicy::default().with_idle_timeout(Duration::from_millis(20)),
+            rate_limiter: None,
+            middleware: MiddlewareStack::default(),
+        };
+        let stalled: EventStream<i32> = Box::pin(
+            futures_util::stream::iter(vec![Ok(1), Ok(2)]).chain(futures_util::stream::pending()),
+        );
+        let out = idle_timeout_stream(stalled, &config, &RequestOptions::default())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(matches!(
+            out[..],
+            [Ok(1), Ok(2), Err(SdkError::StreamIdleTimeout { idle })]
+                if idle == Duration::from_millis(20)
+        ));
+    }
+
     #[test]
     fn test_clamp_retry_policy_caps_retries() {
         let policy = RetryPolicy::default().with_max_retries(999);
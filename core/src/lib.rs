@@ -1,15 +1,35 @@
 use futures_core::Stream;
 use futures_util::StreamExt;
 use futures_util::future::BoxFuture;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
 
+pub mod cancellation;
+pub mod credentials;
+pub mod env;
 pub mod error;
 pub mod http;
+pub mod metrics;
+pub mod middleware;
+pub mod rate_limit;
 pub mod stream_contract;
+pub mod telemetry;
 
-pub use error::{SdkError, StreamInvariantViolation};
-pub use http::{RequestOptions, RetryNetworkRule, RetryPolicy, RetryStatusRule, TimeoutPolicy};
+pub use cancellation::CancellationToken;
+pub use credentials::{
+    AuthHeader, CredentialProvider, EnvCredentials, FileCredentials, RefreshingCredentials,
+    StaticCredentials,
+};
+pub use error::{ApiStatusError, SdkError, StreamInvariantViolation};
+pub use http::{
+    CannedResponse, HttpTransport, InMemoryTransport, NetworkConfig, RecordedRequest,
+    RequestOptions, ReqwestTransport, RetryNetworkRule, RetryPolicy, RetryStatusRule,
+    TimeoutPolicy,
+};
+pub use metrics::{InferenceMetrics, StreamMetrics};
+pub use middleware::{Middleware, MiddlewareStack, Next};
+pub use rate_limit::{RateLimitInfo, RateLimiter};
 pub use stream_contract::{EventOrderValidator, validate_event_sequence};
 
 /// A provider that can fulfill inference requests.
@@ -20,8 +40,11 @@ pub trait InferenceProvider: Send + Sync {
         options: Option<RequestOptions>,
     ) -> BoxFuture<'a, Result<InferenceResult, SdkError>> {
         Box::pin(async move {
+            let response_schema = request.response_schema.clone();
+            let metrics = options.as_ref().and_then(|o| o.metrics.clone());
             let stream = self.stream(request, options).await?;
-            InferenceResult::from_stream(stream).await
+            let result = InferenceResult::from_stream(stream).await?;
+            Ok(result.finish(response_schema, metrics))
         })
     }
 
@@ -48,10 +71,24 @@ pub struct InferenceRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<String>,
 
+    /// Cache the prompt prefix up to and including the system prompt (and tools).
+    /// Requires `system`; Anthropic rejects a breakpoint without one as a `ConfigError`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_cache_breakpoint: Option<CacheBreakpoint>,
+
     /// Available tools for the model to use.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<Vec<Tool>>,
 
+    /// How the model should choose among `tools`. Defaults to provider behavior (auto).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ToolChoice>,
+
+    /// Whether the model may issue several tool calls in one turn.
+    /// `None` keeps the provider default; only applied when tools are present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+
     /// Sampling temperature (0.0 to 1.0).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
@@ -64,6 +101,19 @@ pub struct InferenceRequest {
     /// Providers that support it will use this; others will ignore it.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking_budget: Option<u32>,
+
+    /// Additional sampling controls (stop sequences, nucleus/top-k sampling, seed, penalties).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingParams>,
+
+    /// What to do when the provider cannot honour a requested parameter.
+    /// Defaults to [`UnsupportedParameterPolicy::Warn`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported_parameters: Option<UnsupportedParameterPolicy>,
+
+    /// Constrain the response to JSON matching this schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
 }
 
 // Bon builder
@@ -76,8 +126,14 @@ impl InferenceRequest {
         temperature: Option<f32>,
         max_tokens: Option<u32>,
         #[builder(into)] system: Option<String>,
+        system_cache_breakpoint: Option<CacheBreakpoint>,
         tools: Option<Vec<Tool>>,
+        tool_choice: Option<ToolChoice>,
+        parallel_tool_calls: Option<bool>,
         thinking_budget: Option<u32>,
+        sampling: Option<SamplingParams>,
+        unsupported_parameters: Option<UnsupportedParameterPolicy>,
+        response_schema: Option<ResponseSchema>,
     ) -> Self {
         Self {
             model,
@@ -85,8 +141,14 @@ impl InferenceRequest {
             temperature,
             max_tokens,
             system,
+            system_cache_breakpoint,
             tools,
+            tool_choice,
+            parallel_tool_calls,
             thinking_budget,
+            sampling,
+            unsupported_parameters,
+            response_schema,
         }
     }
 }
@@ -99,6 +161,23 @@ pub struct InferenceMessage {
     // Optional field to link a tool result to a tool call
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_call_id: Option<String>,
+    /// Cache the prompt prefix up to and including this message, where the provider supports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_breakpoint: Option<CacheBreakpoint>,
+}
+
+/// A provider-agnostic prompt-caching hint.
+///
+/// Marks the end of a prompt prefix worth caching. Anthropic translates it into a
+/// `cache_control` marker; providers that cache automatically (OpenAI) ignore it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheBreakpoint {
+    /// The provider's default cache lifetime (5 minutes on Anthropic).
+    #[default]
+    Ephemeral,
+    /// An extended cache lifetime (1 hour on Anthropic).
+    Extended,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -131,6 +210,33 @@ pub enum InferenceContent {
         #[serde(skip_serializing_if = "Option::is_none")]
         signature: Option<String>,
     },
+    /// A thinking block the provider returned encrypted (Anthropic `redacted_thinking`).
+    /// `data` is opaque and must be sent back unchanged in later turns.
+    RedactedThinking {
+        data: String,
+    },
+    /// An image attached to a user message.
+    Image {
+        source: MediaSource,
+    },
+    /// A document (e.g. PDF or plain text) attached to a user message.
+    Document {
+        source: MediaSource,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+    },
+}
+
+/// Where the bytes of an image or document come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MediaSource {
+    /// Inline base64-encoded data with its MIME type (e.g. "image/png", "application/pdf").
+    Base64 { media_type: String, data: String },
+    /// Inline plain text. Only valid for documents.
+    Text { data: String },
+    /// A URL the provider fetches itself.
+    Url { url: String },
 }
 
 /// Normalized definition of a tool.
@@ -141,10 +247,172 @@ pub struct Tool {
     pub input_schema: serde_json::Value,
 }
 
+/// Normalized tool selection strategy.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolChoice {
+    /// The model decides whether to call a tool.
+    Auto,
+    /// The model must not call any tool.
+    None,
+    /// The model must call at least one tool (`required` on OpenAI, `any` on Anthropic).
+    Required,
+    /// The model must call the named tool.
+    Tool { name: String },
+}
+
+/// Provider-agnostic sampling parameters.
+///
+/// Support differs per provider: Anthropic has no `seed` or penalties, OpenAI has no `top_k`.
+/// Parameters a provider cannot honour are handled according to the request's
+/// [`UnsupportedParameterPolicy`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, bon::Builder)]
+pub struct SamplingParams {
+    /// Sequences that stop generation when produced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+
+    /// Nucleus sampling probability mass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// Sample only from the `top_k` most likely tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+
+    /// Seed for best-effort deterministic sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+
+    /// Penalty for tokens that already appeared at all (-2.0 to 2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+
+    /// Penalty proportional to how often a token already appeared (-2.0 to 2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+}
+
+/// A JSON schema the response must conform to (structured output).
+///
+/// OpenAI receives it as `response_format`; Anthropic emulates it by forcing a single call
+/// to a tool named `name` whose input schema is `schema`. Either way the JSON arrives as
+/// text content and is exposed through [`InferenceResult::structured_output`].
+///
+/// On Anthropic the schema root must be an object, `name` must not match a request tool, and
+/// neither `tool_choice` nor `thinking_budget` may be set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSchema {
+    /// Schema name (letters, digits, `_` and `-`).
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The JSON Schema itself.
+    pub schema: serde_json::Value,
+    /// Ask the provider to enforce the schema exactly where supported (OpenAI `strict`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+}
+
+impl ResponseSchema {
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            schema,
+            strict: None,
+        }
+    }
+
+    /// Checks `value` against `schema`, listing every violation in the error.
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), SdkError> {
+        let validator = jsonschema::validator_for(&self.schema).map_err(|err| {
+            SdkError::StructuredOutputError(format!("response schema is invalid: {err}"))
+        })?;
+        let violations = validator
+            .iter_errors(value)
+            .map(|err| match err.instance_path().to_string() {
+                path if path.is_empty() => err.to_string(),
+                path => format!("{path}: {err}"),
+            })
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(SdkError::StructuredOutputError(format!(
+            "response does not match the schema: {}",
+            violations.join("; ")
+        )))
+    }
+}
+
+/// Handling of request parameters the target provider cannot honour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsupportedParameterPolicy {
+    /// Fail the request with [`SdkError::UnsupportedParameter`].
+    Error,
+    /// Drop the parameter and log a warning.
+    #[default]
+    Warn,
+    /// Drop the parameter silently.
+    Drop,
+}
+
+impl UnsupportedParameterPolicy {
+    /// Applies the policy to `parameter`, which `provider` cannot honour.
+    pub fn check(self, provider: &str, parameter: &str) -> Result<(), SdkError> {
+        match self {
+            Self::Error => Err(SdkError::UnsupportedParameter {
+                provider: provider.to_string(),
+                parameter: parameter.to_string(),
+            }),
+            Self::Warn => {
+                tracing::warn!(
+                    provider,
+                    parameter,
+                    "dropping unsupported request parameter"
+                );
+                Ok(())
+            }
+            Self::Drop => Ok(()),
+        }
+    }
+}
+
+/// Token accounting for one response.
+///
+/// Providers differ in what their raw counters include; adapters normalize them so that
+/// `input_tokens` covers every prompt token (cached or not) and `output_tokens` covers every
+/// generated token (including reasoning). The optional fields break those totals down.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
+    /// Prompt tokens written to the provider's prompt cache (Anthropic).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Prompt tokens served from the provider's prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+    /// Output tokens spent on reasoning (OpenAI reasoning models).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
+}
+
+impl Usage {
+    pub fn new(input_tokens: u32, output_tokens: u32) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            ..Self::default()
+        }
+    }
+
+    /// Total billed tokens (`input_tokens + output_tokens`).
+    pub fn total_tokens(&self) -> u32 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -162,6 +430,29 @@ pub struct InferenceResult {
     pub model: String,
     pub stop_reason: Option<StopReason>,
     pub usage: Usage,
+    /// The response text parsed as JSON, when the request carried a `response_schema`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_output: Option<serde_json::Value>,
+    /// The schema the response was requested with; used by [`InferenceResult::parse`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
+    /// Rate-limit headers of the response, when the provider sent them.
+    ///
+    /// Only filled by `InferenceProvider::complete`; results built with
+    /// [`InferenceResult::from_stream`] leave it `None` because stream events do not carry
+    /// headers. An attached `RateLimiter` still observes the headers of streaming responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitInfo>,
+    /// Timings of the call, when the request carried a `StreamMetrics` recorder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<InferenceMetrics>,
+}
+
+/// Argument accumulation state for one streamed tool call.
+struct PendingToolCall {
+    index: u32,
+    slot: usize,
+    json: String,
 }
 
 impl InferenceResult {
@@ -173,31 +464,83 @@ impl InferenceResult {
     }
 
     fn finalize_pending_tool(
-        current_tool_id: &mut Option<String>,
-        current_tool_name: &mut Option<String>,
-        current_tool_json: &mut String,
-        content_parts: &mut Vec<InferenceContent>,
+        pending: PendingToolCall,
+        content_parts: &mut [InferenceContent],
     ) -> Result<(), SdkError> {
-        if current_tool_id.is_none() && current_tool_name.is_none() && current_tool_json.is_empty()
-        {
-            return Ok(());
+        let parsed = Self::parse_tool_input(&pending.json)?;
+        if let Some(InferenceContent::ToolUse { input, .. }) = content_parts.get_mut(pending.slot) {
+            *input = parsed;
         }
+        Ok(())
+    }
 
-        let id = current_tool_id.take().ok_or_else(|| {
-            SdkError::StreamInvariantViolation(StreamInvariantViolation::ToolCallMissingId)
-        })?;
-        let name = current_tool_name.take().ok_or_else(|| {
-            SdkError::StreamInvariantViolation(StreamInvariantViolation::ToolCallMissingName)
-        })?;
-        let input = Self::parse_tool_input(current_tool_json)?;
-        content_parts.push(InferenceContent::ToolUse { id, name, input });
-        current_tool_json.clear();
+    fn finalize_pending_tools(
+        pending_tools: &mut Vec<PendingToolCall>,
+        content_parts: &mut [InferenceContent],
+    ) -> Result<(), SdkError> {
+        for pending in pending_tools.drain(..) {
+            Self::finalize_pending_tool(pending, content_parts)?;
+        }
         Ok(())
     }
 
+    /// Shared `complete` post-processing: applies the request's response schema and attaches
+    /// the final metrics snapshot.
+    #[doc(hidden)]
+    pub fn finish(
+        mut self,
+        response_schema: Option<ResponseSchema>,
+        metrics: Option<StreamMetrics>,
+    ) -> Self {
+        if let Some(schema) = response_schema {
+            self.apply_response_schema(schema);
+        }
+        if let Some(metrics) = metrics {
+            metrics.record_completion(&mut self);
+        }
+        self
+    }
+
+    /// Records the requested schema and parses the response text into `structured_output`.
+    ///
+    /// Text that is not valid JSON leaves `structured_output` empty; [`InferenceResult::parse`]
+    /// reports why.
+    pub fn apply_response_schema(&mut self, schema: ResponseSchema) {
+        self.structured_output = serde_json::from_str(self.text().trim()).ok();
+        self.response_schema = Some(schema);
+    }
+
+    /// Validates `structured_output` against the requested schema, then deserializes it into `T`.
+    ///
+    /// Providers do not always enforce the schema (Anthropic emulates it with a tool call, and
+    /// OpenAI only in strict mode), so violations of `required`, `enum`, `additionalProperties`,
+    /// and the like are checked here. Schema violations and `T` mismatches are reported as
+    /// [`SdkError::StructuredOutputError`].
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, SdkError> {
+        let Some(value) = &self.structured_output else {
+            let reason = match &self.response_schema {
+                Some(_) => match serde_json::from_str::<serde_json::Value>(self.text().trim()) {
+                    Err(err) => format!("response is not valid JSON: {err}"),
+                    Ok(_) => "structured output was not recorded".to_string(),
+                },
+                None => "request did not specify a response schema".to_string(),
+            };
+            return Err(SdkError::StructuredOutputError(reason));
+        };
+        if let Some(schema) = &self.response_schema {
+            schema.validate(value)?;
+        }
+        serde_json::from_value(value.clone()).map_err(|err| {
+            SdkError::StructuredOutputError(format!(
+                "response does not match the expected type: {err}"
+            ))
+        })
+    }
+
     /// Helper to extract only `InferenceContent::Text` blocks combined.
     ///
-    /// This intentionally excludes `Thinking`, `ToolUse`, and `ToolResult` content.
+    /// This intentionally excludes `Thinking`, `RedactedThinking`, `ToolUse`, `ToolResult`,
+    /// `Image`, and `Document` content.
     /// Consumers that need full semantic output should inspect `self.content` directly.
     pub fn text(&self) -> String {
         self.content
@@ -215,15 +558,14 @@ impl InferenceResult {
         let mut content_parts = Vec::new();
         let mut model = String::new();
         let mut stop_reason = None;
-        let mut usage = Usage {
-            input_tokens: 0,
-            output_tokens: 0,
-        };
+        let mut usage = Usage::default();
 
-        // Tool call accumulation state.
-        let mut current_tool_id: Option<String> = None;
-        let mut current_tool_name: Option<String> = None;
-        let mut current_tool_json: String = String::new();
+        // Tool calls in flight, keyed by provider index. Each one reserves its slot in
+        // `content_parts` at start so interleaved calls keep their original order.
+        let mut pending_tools: Vec<PendingToolCall> = Vec::new();
+        // Set when a content block opens or closes so the next delta starts a new part
+        // instead of merging into the previous block of the same kind.
+        let mut block_boundary = false;
         let mut event_validator = EventOrderValidator::new();
 
         while let Some(event_res) = stream.next().await {
@@ -237,80 +579,90 @@ impl InferenceResult {
                         InferenceEvent::MessageStart { model: m, .. } => {
                             model = m;
                         }
-                        InferenceEvent::MessageDelta { content } => {
-                            if let Some(InferenceContent::Text { text }) = content_parts.last_mut()
+                        InferenceEvent::ContentBlockStart { .. } => {
+                            block_boundary = true;
+                        }
+                        InferenceEvent::ContentBlockStop { index } => {
+                            block_boundary = true;
+                            if let Some(position) = pending_tools
+                                .iter()
+                                .position(|pending| pending.index == index)
                             {
-                                text.push_str(&content);
-                            } else {
-                                content_parts.push(InferenceContent::Text { text: content });
+                                let pending = pending_tools.remove(position);
+                                Self::finalize_pending_tool(pending, &mut content_parts)?;
+                            }
+                        }
+                        InferenceEvent::MessageDelta { content } => {
+                            match content_parts.last_mut() {
+                                Some(InferenceContent::Text { text }) if !block_boundary => {
+                                    text.push_str(&content);
+                                }
+                                _ => content_parts.push(InferenceContent::Text { text: content }),
                             }
+                            block_boundary = false;
                         }
                         InferenceEvent::ThinkingDelta { content } => {
-                            if let Some(InferenceContent::Thinking { content: text, .. }) =
-                                content_parts.last_mut()
-                            {
-                                text.push_str(&content);
-                            } else {
-                                content_parts.push(InferenceContent::Thinking {
+                            match content_parts.last_mut() {
+                                Some(InferenceContent::Thinking { content: text, .. })
+                                    if !block_boundary =>
+                                {
+                                    text.push_str(&content);
+                                }
+                                _ => content_parts.push(InferenceContent::Thinking {
                                     content,
                                     signature: None,
-                                });
+                                }),
                             }
+                            block_boundary = false;
                         }
                         InferenceEvent::ThinkingSignatureDelta { signature } => {
-                            if let Some(InferenceContent::Thinking {
-                                signature: existing,
-                                ..
-                            }) = content_parts.last_mut()
-                            {
-                                if let Some(existing) = existing.as_mut() {
-                                    existing.push_str(&signature);
-                                } else {
-                                    *existing = Some(signature);
+                            match content_parts.last_mut() {
+                                Some(InferenceContent::Thinking {
+                                    signature: existing,
+                                    ..
+                                }) if !block_boundary => {
+                                    if let Some(existing) = existing.as_mut() {
+                                        existing.push_str(&signature);
+                                    } else {
+                                        *existing = Some(signature);
+                                    }
                                 }
-                            } else {
-                                content_parts.push(InferenceContent::Thinking {
+                                _ => content_parts.push(InferenceContent::Thinking {
                                     content: String::new(),
                                     signature: Some(signature),
-                                });
+                                }),
                             }
+                            block_boundary = false;
                         }
-                        InferenceEvent::ToolCallStart { id, name } => {
-                            // Providers should not interleave tool-call streams, but if they do,
-                            // close the previous pending call before starting the next one.
-                            Self::finalize_pending_tool(
-                                &mut current_tool_id,
-                                &mut current_tool_name,
-                                &mut current_tool_json,
-                                &mut content_parts,
-                            )?;
-                            current_tool_id = Some(id);
-                            current_tool_name = Some(name);
-                            current_tool_json.clear();
+                        InferenceEvent::RedactedThinking { data } => {
+                            content_parts.push(InferenceContent::RedactedThinking { data });
+                            block_boundary = false;
                         }
-                        InferenceEvent::ToolCallDelta { delta } => {
-                            if current_tool_id.is_none() || current_tool_name.is_none() {
-                                return Err(
-                                    StreamInvariantViolation::ToolCallDeltaBeforeStart.into()
-                                );
-                            }
-                            current_tool_json.push_str(&delta);
+                        InferenceEvent::ToolCallStart { index, id, name } => {
+                            pending_tools.push(PendingToolCall {
+                                index,
+                                slot: content_parts.len(),
+                                json: String::new(),
+                            });
+                            content_parts.push(InferenceContent::ToolUse {
+                                id,
+                                name,
+                                input: serde_json::Value::Null,
+                            });
+                        }
+                        InferenceEvent::ToolCallDelta { index, delta } => {
+                            let pending = pending_tools
+                                .iter_mut()
+                                .find(|pending| pending.index == index)
+                                .ok_or(StreamInvariantViolation::ToolCallDeltaBeforeStart)?;
+                            pending.json.push_str(&delta);
                         }
                         InferenceEvent::MessageEnd {
-                            input_tokens,
-                            output_tokens,
+                            usage: u,
                             stop_reason: sr,
                         } => {
-                            Self::finalize_pending_tool(
-                                &mut current_tool_id,
-                                &mut current_tool_name,
-                                &mut current_tool_json,
-                                &mut content_parts,
-                            )?;
-                            usage = Usage {
-                                input_tokens,
-                                output_tokens,
-                            };
+                            Self::finalize_pending_tools(&mut pending_tools, &mut content_parts)?;
+                            usage = u;
                             stop_reason = sr;
                         }
                     }
@@ -326,10 +678,25 @@ impl InferenceResult {
             model,
             stop_reason,
             usage,
+            structured_output: None,
+            response_schema: None,
+            rate_limit: None,
+            metrics: None,
         })
     }
 }
 
+/// The kind of content carried by a streamed content block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ContentBlockKind {
+    Text,
+    Thinking,
+    RedactedThinking,
+    ToolUse,
+}
+
 /// Events emitted during a streaming inference response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -342,20 +709,33 @@ pub enum InferenceEvent {
         /// The provider attempting to fulfill this request.
         provider_id: String,
     },
+    /// A content block opened. Unindexed deltas until the matching `ContentBlockStop`
+    /// belong to this block.
+    ContentBlockStart { index: u32, kind: ContentBlockKind },
+    /// A content block closed. For tool calls, the arguments are complete at this point.
+    ContentBlockStop { index: u32 },
     /// A text delta for the message content.
     MessageDelta { content: String },
     /// A thought process delta (for reasoning models).
     ThinkingDelta { content: String },
     /// A signature delta for a thinking block (Anthropic-compatible providers).
     ThinkingSignatureDelta { signature: String },
+    /// A complete redacted thinking block; `data` is opaque and arrives in one piece.
+    RedactedThinking { data: String },
     /// A tool call started.
-    ToolCallStart { id: String, name: String },
+    ///
+    /// `index` is the content block index of the call; parallel calls may interleave
+    /// their argument deltas, so every delta carries the index of the call it extends.
+    ToolCallStart {
+        index: u32,
+        id: String,
+        name: String,
+    },
     /// A delta for a tool call argument (JSON fragment).
-    ToolCallDelta { delta: String },
+    ToolCallDelta { index: u32, delta: String },
     /// The end of a message response, including usage statistics.
     MessageEnd {
-        input_tokens: u32,
-        output_tokens: u32,
+        usage: Usage,
         stop_reason: Option<StopReason>,
     },
 }
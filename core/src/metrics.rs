@@ -0,0 +1,261 @@
+//! Latency and throughput measurements for a single inference call.
+//!
+//! Attach a [`StreamMetrics`] recorder with `RequestOptions::with_metrics`. The HTTP layer records
+//! the request start, retries, and streamed body bytes; [`measure_stream`] records event timings
+//! as the caller consumes the stream. Read [`StreamMetrics::snapshot`] at any point for the
+//! numbers so far; `complete` also attaches the final snapshot as `InferenceResult::metrics`.
+
+use crate::telemetry::is_content;
+use crate::{InferenceEvent, InferenceResult, InferenceStream};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// A point-in-time view of a call's timings. Durations are measured from `started_at`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InferenceMetrics {
+    /// When the first HTTP attempt was sent.
+    pub started_at: Option<SystemTime>,
+    /// Delay until the first stream event of any kind (e.g. `MessageStart`).
+    pub time_to_first_event: Option<Duration>,
+    /// Delay until the first text, thinking, or tool-call event; for non-streaming calls, until
+    /// the response body arrived.
+    pub time_to_first_token: Option<Duration>,
+    /// Delay until the response ended; `None` while it is still in progress.
+    pub duration: Option<Duration>,
+    /// Output tokens reported by the provider, once known.
+    pub output_tokens: Option<u32>,
+    /// Output tokens per second between the first token and the end of the response (or the
+    /// whole call when no token was streamed).
+    pub tokens_per_second: Option<f64>,
+    /// Retried HTTP attempts plus re-issued streams.
+    pub retries: u32,
+    /// Response body bytes received.
+    pub bytes_received: u64,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    started: Option<(Instant, SystemTime)>,
+    first_event: Option<Instant>,
+    first_token: Option<Instant>,
+    ended: Option<Instant>,
+    output_tokens: Option<u32>,
+    retries: u32,
+    bytes_received: u64,
+}
+
+/// A shared recorder for one call's [`InferenceMetrics`]. Clones record into the same state.
+#[derive(Clone, Default)]
+pub struct StreamMetrics {
+    state: Arc<Mutex<MetricsState>>,
+}
+
+impl fmt::Debug for StreamMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StreamMetrics")
+            .field(&self.snapshot())
+            .finish()
+    }
+}
+
+impl StreamMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The measurements recorded so far.
+    pub fn snapshot(&self) -> InferenceMetrics {
+        let state = self.lock();
+        let Some((start, started_at)) = state.started else {
+            return InferenceMetrics {
+                retries: state.retries,
+                bytes_received: state.bytes_received,
+                ..Default::default()
+            };
+        };
+        let since_start = |at: Option<Instant>| at.map(|at| at.saturating_duration_since(start));
+        let tokens_per_second = match (state.output_tokens, state.ended) {
+            (Some(tokens), Some(ended)) => {
+                let generation =
+                    ended.saturating_duration_since(state.first_token.unwrap_or(start));
+                (!generation.is_zero()).then(|| f64::from(tokens) / generation.as_secs_f64())
+            }
+            _ => None,
+        };
+        InferenceMetrics {
+            started_at: Some(started_at),
+            time_to_first_event: since_start(state.first_event),
+            time_to_first_token: since_start(state.first_token),
+            duration: since_start(state.ended),
+            output_tokens: state.output_tokens,
+            tokens_per_second,
+            retries: state.retries,
+            bytes_received: state.bytes_received,
+        }
+    }
+
+    /// Marks the response as finished with its token usage and attaches the final snapshot.
+    pub fn record_completion(&self, result: &mut InferenceResult) {
+        self.finish(Some(result.usage.output_tokens));
+        result.metrics = Some(self.snapshot());
+    }
+
+    /// Starts the clock; later calls (e.g. for a re-issued stream) keep the first start.
+    pub(crate) fn start(&self) {
+        self.lock()
+            .started
+            .get_or_insert_with(|| (Instant::now(), SystemTime::now()));
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.lock().retries += 1;
+    }
+
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        self.lock().bytes_received += bytes as u64;
+    }
+
+    /// Records a complete non-streaming body, which delivers every token at once.
+    pub(crate) fn record_response(&self, bytes: usize) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        state.bytes_received += bytes as u64;
+        state.first_event.get_or_insert(now);
+        state.first_token.get_or_insert(now);
+    }
+
+    fn record_event(&self, event: &InferenceEvent) {
+        let now = Instant::now();
+        let mut state = self.lock();
+        state.first_event.get_or_insert(now);
+        if is_content(event) {
+            state.first_token.get_or_insert(now);
+        }
+        if let InferenceEvent::MessageEnd { usage, .. } = event {
+            state.output_tokens = Some(usage.output_tokens);
+            state.ended.get_or_insert(now);
+        }
+    }
+
+    fn finish(&self, output_tokens: Option<u32>) {
+        let mut state = self.lock();
+        state.ended.get_or_insert_with(Instant::now);
+        if output_tokens.is_some() {
+            state.output_tokens = output_tokens;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MetricsState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Records event timings of `stream` into `metrics` as it is consumed. The response counts as
+/// finished at `MessageEnd`, at the first error, or when the stream ends.
+pub fn measure_stream(stream: InferenceStream, metrics: Option<StreamMetrics>) -> InferenceStream {
+    let Some(metrics) = metrics else {
+        return stream;
+    };
+    metrics.start();
+    Box::pin(futures_util::stream::unfold(
+        Some((stream, metrics)),
+        |state| async move {
+            let (mut stream, metrics) = state?;
+            match stream.next().await {
+                Some(Ok(event)) => {
+                    metrics.record_event(&event);
+                    Some((Ok(event), Some((stream, metrics))))
+                }
+                Some(Err(error)) => {
+                    metrics.finish(None);
+                    Some((Err(error), Some((stream, metrics))))
+                }
+                None => {
+                    metrics.finish(None);
+                    None
+                }
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SdkError, StopReason, Usage};
+
+    #[tokio::test]
+    async fn test_measure_stream_records_timings_incrementally() {
+        let metrics = StreamMetrics::new();
+        let events: Vec<Result<InferenceEvent, SdkError>> = vec![
+            Ok(InferenceEvent::MessageStart {
+                role: "assistant".to_string(),
+                model: "model".to_string(),
+                provider_id: "test".to_string(),
+            }),
+            Ok(InferenceEvent::MessageDelta {
+                content: "hi".to_string(),
+            }),
+            Ok(InferenceEvent::MessageEnd {
+                usage: Usage::new(5, 40),
+                stop_reason: Some(StopReason::EndTurn),
+            }),
+        ];
+        let mut stream = measure_stream(
+            Box::pin(futures_util::stream::iter(events)),
+            Some(metrics.clone()),
+        );
+
+        stream.next().await.unwrap().unwrap();
+        let after_start = metrics.snapshot();
+        assert!(after_start.started_at.is_some());
+        assert!(after_start.time_to_first_event.is_some());
+        assert_eq!(after_start.time_to_first_token, None);
+        assert_eq!(after_start.duration, None);
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        stream.next().await.unwrap().unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        stream.next().await.unwrap().unwrap();
+        assert!(stream.next().await.is_none());
+
+        let done = metrics.snapshot();
+        let first_token = done.time_to_first_token.unwrap();
+        assert!(first_token >= done.time_to_first_event.unwrap());
+        assert!(done.duration.unwrap() > first_token);
+        assert_eq!(done.output_tokens, Some(40));
+        assert!(done.tokens_per_second.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_record_completion_attaches_snapshot() {
+        let metrics = StreamMetrics::new();
+        metrics.start();
+        metrics.record_retry();
+        let mut result = InferenceResult {
+            content: vec![],
+            model: "model".to_string(),
+            stop_reason: None,
+            usage: Usage::new(1, 2),
+            structured_output: None,
+            response_schema: None,
+            rate_limit: None,
+            metrics: None,
+        };
+
+        metrics.record_response(128);
+        metrics.record_completion(&mut result);
+        let recorded = result.metrics.unwrap();
+        assert_eq!(recorded.retries, 1);
+        assert_eq!(recorded.bytes_received, 128);
+        assert_eq!(recorded.output_tokens, Some(2));
+        let first_token = recorded.time_to_first_token.expect("response time");
+        assert_eq!(recorded.time_to_first_event, Some(first_token));
+        assert!(recorded.duration.unwrap() >= first_token);
+    }
+}
@@ -0,0 +1,115 @@
+//! Request/response middleware around every HTTP attempt.
+//!
+//! Middleware runs inside the retry loop of [`send_with_retry`](crate::http::send_with_retry),
+//! so each attempt passes through the whole chain. Layers run in registration order: the first
+//! registered sees the request first and the response last.
+
+use crate::error::SdkError;
+use crate::http::HttpTransport;
+use futures_util::future::BoxFuture;
+use std::fmt;
+use std::sync::Arc;
+
+/// One layer of the chain. Call `next.run(request)` to continue, or return a response or error
+/// directly to short-circuit.
+///
+/// ```
+/// use futures_util::future::BoxFuture;
+/// use inference_sdk_core::{Middleware, Next, SdkError};
+///
+/// struct AuditLog;
+///
+/// impl Middleware for AuditLog {
+///     fn handle<'a>(
+///         &'a self,
+///         request: reqwest::Request,
+///         next: Next<'a>,
+///     ) -> BoxFuture<'a, Result<reqwest::Response, SdkError>> {
+///         Box::pin(async move {
+///             let url = request.url().clone();
+///             let response = next.run(request).await?;
+///             tracing::info!(%url, status = response.status().as_u16(), "provider call");
+///             Ok(response)
+///         })
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(
+        &'a self,
+        request: reqwest::Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<reqwest::Response, SdkError>>;
+}
+
+/// The rest of the chain after the current layer.
+pub struct Next<'a> {
+    transport: &'a dyn HttpTransport,
+    layers: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    /// Passes `request` to the next layer, or sends it through the transport when no layers are
+    /// left.
+    pub fn run(
+        self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'a, Result<reqwest::Response, SdkError>> {
+        match self.layers.split_first() {
+            Some((layer, rest)) => layer.handle(
+                request,
+                Next {
+                    transport: self.transport,
+                    layers: rest,
+                },
+            ),
+            None => self.transport.execute(request),
+        }
+    }
+}
+
+/// An ordered, cheaply cloneable list of middleware layers.
+#[derive(Clone, Default)]
+pub struct MiddlewareStack {
+    layers: Vec<Arc<dyn Middleware>>,
+}
+
+impl fmt::Debug for MiddlewareStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareStack")
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl MiddlewareStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `middleware` as the innermost layer so far.
+    pub fn push(&mut self, middleware: impl Middleware) {
+        self.layers.push(Arc::new(middleware));
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Runs `request` through every layer and then sends it with `transport`.
+    pub fn run<'a>(
+        &'a self,
+        transport: &'a dyn HttpTransport,
+        request: reqwest::Request,
+    ) -> BoxFuture<'a, Result<reqwest::Response, SdkError>> {
+        Next {
+            transport,
+            layers: &self.layers,
+        }
+        .run(request)
+    }
+}
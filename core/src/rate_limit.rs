@@ -0,0 +1,535 @@
+//! Provider rate-limit headers and client-side throttling.
+//!
+//! Anthropic reports `anthropic-ratelimit-{requests,tokens}-{limit,remaining,reset}` with RFC 3339
+//! reset timestamps; OpenAI reports `x-ratelimit-{limit,remaining,reset}-{requests,tokens}` with
+//! durations such as `6m0s`. Both are normalized into [`RateLimitInfo`].
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_EXHAUSTED_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A snapshot of the provider's rate-limit headers from one response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_remaining: Option<u64>,
+    /// Time until the request window resets, measured when the response arrived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_reset: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_remaining: Option<u64>,
+    /// Time until the token window resets, measured when the response arrived.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_reset: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parses Anthropic or OpenAI rate-limit headers; `None` when the response carries none.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let now = SystemTime::now();
+        let info = Self {
+            requests_limit: number(
+                headers,
+                "anthropic-ratelimit-requests-limit",
+                "x-ratelimit-limit-requests",
+            ),
+            requests_remaining: number(
+                headers,
+                "anthropic-ratelimit-requests-remaining",
+                "x-ratelimit-remaining-requests",
+            ),
+            requests_reset: reset(
+                headers,
+                "anthropic-ratelimit-requests-reset",
+                "x-ratelimit-reset-requests",
+                now,
+            ),
+            tokens_limit: number(
+                headers,
+                "anthropic-ratelimit-tokens-limit",
+                "x-ratelimit-limit-tokens",
+            ),
+            tokens_remaining: number(
+                headers,
+                "anthropic-ratelimit-tokens-remaining",
+                "x-ratelimit-remaining-tokens",
+            ),
+            tokens_reset: reset(
+                headers,
+                "anthropic-ratelimit-tokens-reset",
+                "x-ratelimit-reset-tokens",
+                now,
+            ),
+        };
+        (info != Self::default()).then_some(info)
+    }
+
+    /// Whether either budget was reported as fully spent.
+    pub fn is_exhausted(&self) -> bool {
+        self.requests_remaining == Some(0) || self.tokens_remaining == Some(0)
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok().map(str::trim)
+}
+
+fn number(headers: &HeaderMap, anthropic: &str, openai: &str) -> Option<u64> {
+    header(headers, anthropic)
+        .or_else(|| header(headers, openai))?
+        .parse()
+        .ok()
+}
+
+fn reset(headers: &HeaderMap, anthropic: &str, openai: &str, now: SystemTime) -> Option<Duration> {
+    if let Some(value) = header(headers, anthropic) {
+        let at = parse_rfc3339(value)?;
+        return Some(at.duration_since(now).unwrap_or_default());
+    }
+    parse_duration(header(headers, openai)?)
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.fff](Z|±HH:MM)`, rejecting out-of-range fields.
+fn parse_rfc3339(value: &str) -> Option<SystemTime> {
+    let (date, time) = value.split_once(['T', 't', ' '])?;
+    let mut date_parts = date.split('-');
+    let year = digits(date_parts.next()?, 4)?;
+    let month = digits(date_parts.next()?, 2)?;
+    let day = digits(date_parts.next()?, 2)?;
+    if date_parts.next().is_some()
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+    {
+        return None;
+    }
+
+    let (clock, offset_secs) = match time.strip_suffix(['Z', 'z']) {
+        Some(clock) => (clock, 0),
+        None => {
+            let (clock, offset) = time.split_at(time.rfind(['+', '-'])?);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let (hours, minutes) = (digits(hours, 2)?, digits(minutes, 2)?);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            (clock, sign * (hours * 3600 + minutes * 60))
+        }
+    };
+    let (hms, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    let mut hms_parts = hms.split(':');
+    let hour = digits(hms_parts.next()?, 2)?;
+    let minute = digits(hms_parts.next()?, 2)?;
+    // 60 is a leap second.
+    let second = digits(hms_parts.next()?, 2)?;
+    if hms_parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let nanos = match fraction {
+        "" if clock.contains('.') => return None,
+        "" => 0,
+        fraction if fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0<9}", &fraction[..fraction.len().min(9)])
+                .parse()
+                .ok()?
+        }
+        _ => return None,
+    };
+
+    let secs = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+        - offset_secs;
+    Some(UNIX_EPOCH + Duration::new(u64::try_from(secs).ok()?, nanos))
+}
+
+/// Parses exactly `len` ASCII digits.
+fn digits(value: &str, len: usize) -> Option<i64> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Parses Go-style durations such as `1s`, `6m0s`, `20ms`, or `1h2m3.5s`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut rest = value;
+    let mut total = 0.0;
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let unit_start = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (amount, tail) = rest.split_at(unit_start);
+        let amount: f64 = amount.parse().ok()?;
+        let unit_end = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, next) = tail.split_at(unit_end);
+        let scale = match unit {
+            "h" => 3600.0,
+            "m" => 60.0,
+            "s" => 1.0,
+            "ms" => 1e-3,
+            "us" | "µs" => 1e-6,
+            "ns" => 1e-9,
+            _ => return None,
+        };
+        total += amount * scale;
+        rest = next;
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+/// A client-side limiter shared by every client it is attached to.
+///
+/// It waits out the server-reported window when the remaining request or token budget drops to
+/// the configured reserve, and can additionally enforce a local requests-per-minute token bucket.
+/// When the server reports a budget without a reset time, the limiter waits a fixed backoff
+/// (one second by default) instead. Clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    inner: Arc<Mutex<LimiterState>>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    bucket: Option<TokenBucket>,
+    request_reserve: u64,
+    token_reserve: u64,
+    exhausted_backoff: Option<Duration>,
+    requests: Window,
+    tokens: Window,
+}
+
+/// The last server-reported budget for one dimension (requests or tokens).
+#[derive(Debug, Default)]
+struct Window {
+    remaining: Option<u64>,
+    reset_at: Option<Instant>,
+}
+
+impl Window {
+    fn observe(
+        &mut self,
+        remaining: Option<u64>,
+        reset: Option<Duration>,
+        fallback: Duration,
+        now: Instant,
+    ) {
+        if remaining.is_some() {
+            self.remaining = remaining;
+            self.reset_at = Some(now + reset.unwrap_or(fallback));
+        }
+    }
+
+    /// How long to wait before the budget is above `reserve` again, if at all.
+    fn wait(&mut self, reserve: u64, now: Instant) -> Option<Duration> {
+        let reset_at = self.reset_at?;
+        if reset_at <= now {
+            *self = Self::default();
+            return None;
+        }
+        self.remaining
+            .is_some_and(|remaining| remaining <= reserve)
+            .then(|| reset_at - now)
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    per_second: f64,
+    available: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn take(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.per_second).min(self.capacity);
+        self.refilled_at = now;
+        if self.available >= 1.0 {
+            self.available -= 1.0;
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            (1.0 - self.available) / self.per_second,
+        ))
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also enforce a local token bucket of `requests_per_minute`, allowing bursts up to that size.
+    pub fn with_requests_per_minute(self, requests_per_minute: u32) -> Self {
+        let capacity = f64::from(requests_per_minute.max(1));
+        self.state().bucket = Some(TokenBucket {
+            capacity,
+            per_second: capacity / 60.0,
+            available: capacity,
+            refilled_at: Instant::now(),
+        });
+        self
+    }
+
+    /// Hold requests back once the server reports this many requests or fewer remaining.
+    pub fn with_request_reserve(self, requests: u64) -> Self {
+        self.state().request_reserve = requests;
+        self
+    }
+
+    /// Hold requests back once the server reports this many tokens or fewer remaining.
+    pub fn with_token_reserve(self, tokens: u64) -> Self {
+        self.state().token_reserve = tokens;
+        self
+    }
+
+    /// How long to hold requests back when the budget is spent but the server sent no reset time.
+    pub fn with_exhausted_backoff(self, backoff: Duration) -> Self {
+        self.state().exhausted_backoff = Some(backoff);
+        self
+    }
+
+    /// Waits until a request may be sent, then counts it against the budget.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = self.state().try_acquire(Instant::now());
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Records the budget a response reported.
+    pub fn observe(&self, info: &RateLimitInfo) {
+        let now = Instant::now();
+        let mut state = self.state();
+        let fallback = state.exhausted_backoff.unwrap_or(DEFAULT_EXHAUSTED_BACKOFF);
+        state
+            .requests
+            .observe(info.requests_remaining, info.requests_reset, fallback, now);
+        state
+            .tokens
+            .observe(info.tokens_remaining, info.tokens_reset, fallback, now);
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl LimiterState {
+    fn try_acquire(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(wait) = self.requests.wait(self.request_reserve, now) {
+            return Some(wait);
+        }
+        if let Some(wait) = self.tokens.wait(self.token_reserve, now) {
+            return Some(wait);
+        }
+        if let Some(bucket) = &mut self.bucket
+            && let Some(wait) = bucket.take(now)
+        {
+            return Some(wait);
+        }
+        // Count this request against the last reported budget so concurrent callers see it.
+        if let Some(remaining) = &mut self.requests.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().expect("valid header value"));
+        }
+        map
+    }
+
+    #[test]
+    fn test_parses_openai_headers() {
+        let info = RateLimitInfo::from_headers(&headers(&[
+            ("x-ratelimit-limit-requests", "500"),
+            ("x-ratelimit-remaining-requests", "499"),
+            ("x-ratelimit-reset-requests", "120ms"),
+            ("x-ratelimit-limit-tokens", "30000"),
+            ("x-ratelimit-remaining-tokens", "0"),
+            ("x-ratelimit-reset-tokens", "6m0.5s"),
+        ]))
+        .expect("headers present");
+
+        assert_eq!(info.requests_limit, Some(500));
+        assert_eq!(info.requests_remaining, Some(499));
+        assert_eq!(info.requests_reset, Some(Duration::from_millis(120)));
+        assert_eq!(info.tokens_reset, Some(Duration::from_millis(360_500)));
+        assert!(info.is_exhausted());
+    }
+
+    #[test]
+    fn test_parses_anthropic_headers() {
+        let info = RateLimitInfo::from_headers(&headers(&[
+            ("anthropic-ratelimit-requests-limit", "50"),
+            ("anthropic-ratelimit-requests-remaining", "49"),
+            ("anthropic-ratelimit-requests-reset", "2000-01-01T00:00:00Z"),
+            ("anthropic-ratelimit-tokens-remaining", "1000"),
+        ]))
+        .expect("headers present");
+
+        assert_eq!(info.requests_limit, Some(50));
+        assert_eq!(info.requests_remaining, Some(49));
+        // Resets in the past clamp to zero.
+        assert_eq!(info.requests_reset, Some(Duration::ZERO));
+        assert_eq!(info.tokens_remaining, Some(1000));
+        assert!(!info.is_exhausted());
+        assert_eq!(RateLimitInfo::from_headers(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_parses_rfc3339_offsets_and_fractions() {
+        let utc = parse_rfc3339("2024-03-01T12:00:00Z").expect("valid timestamp");
+        assert_eq!(
+            utc.duration_since(UNIX_EPOCH).unwrap(),
+            Duration::from_secs(1_709_294_400)
+        );
+        assert_eq!(
+            parse_rfc3339("2024-03-01T14:00:00.250+02:00"),
+            Some(utc + Duration::from_millis(250))
+        );
+        assert_eq!(
+            parse_rfc3339("2024-03-01T06:30:00.0000000015-05:30"),
+            Some(utc + Duration::from_nanos(1))
+        );
+        assert_eq!(
+            parse_rfc3339("2024-02-29t12:00:00z").map(|at| utc.duration_since(at).unwrap()),
+            Some(Duration::from_secs(86_400))
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_rfc3339() {
+        for value in [
+            "",
+            "2024-03-01",
+            "2024-03-01T12:00:00",
+            "2024-13-01T12:00:00Z",
+            "2023-02-29T12:00:00Z",
+            "2024-04-31T12:00:00Z",
+            "2024-03-01T24:00:00Z",
+            "2024-03-01T12:60:00Z",
+            "2024-03-01T12:00:61Z",
+            "2024-03-01T12:00:00.Z",
+            "2024-03-01T12:00:00.5xZ",
+            "2024-03-01T12:00:00+24:00",
+            "2024-03-01T12:00:00+02",
+            "2024-3-01T12:00:00Z",
+            "+024-03-01T12:00:00Z",
+            "2024-03-01-02T12:00:00Z",
+            "2024-03-01T12:00:00:00Z",
+        ] {
+            assert_eq!(parse_rfc3339(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_parses_go_durations() {
+        assert_eq!(
+            parse_duration("1h2m3.5s"),
+            Some(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_duration("20ms"), Some(Duration::from_millis(20)));
+        assert_eq!(parse_duration("1.5us"), Some(Duration::from_nanos(1_500)));
+        for value in ["", "5", "s", "1x", "1.5.2s", "-1s", "1s 2s"] {
+            assert_eq!(parse_duration(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_limiter_waits_for_exhausted_window_and_counts_requests() {
+        let limiter = RateLimiter::new();
+        limiter.observe(&RateLimitInfo {
+            requests_remaining: Some(1),
+            requests_reset: Some(Duration::from_secs(10)),
+            ..Default::default()
+        });
+
+        let now = Instant::now();
+        let mut state = limiter.state();
+        assert_eq!(state.try_acquire(now), None);
+        let wait = state.try_acquire(now).expect("budget spent");
+        assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+        // Once the window resets, requests flow again.
+        assert_eq!(state.try_acquire(now + Duration::from_secs(11)), None);
+    }
+
+    #[test]
+    fn test_limiter_backs_off_when_exhausted_without_reset() {
+        let limiter = RateLimiter::new();
+        limiter.observe(&RateLimitInfo {
+            tokens_remaining: Some(0),
+            ..Default::default()
+        });
+        let now = Instant::now();
+        let wait = limiter.state().try_acquire(now).expect("budget spent");
+        assert!(wait <= DEFAULT_EXHAUSTED_BACKOFF);
+
+        let limiter = RateLimiter::new().with_exhausted_backoff(Duration::from_secs(5));
+        limiter.observe(&RateLimitInfo {
+            requests_remaining: Some(0),
+            ..Default::default()
+        });
+        let now = Instant::now();
+        let mut state = limiter.state();
+        let wait = state.try_acquire(now).expect("budget spent");
+        assert!(wait > Duration::from_secs(4) && wait <= Duration::from_secs(5));
+        assert_eq!(state.try_acquire(now + Duration::from_secs(6)), None);
+    }
+
+    #[test]
+    fn test_token_bucket_spaces_requests() {
+        let limiter = RateLimiter::new().with_requests_per_minute(2);
+        let mut state = limiter.state();
+        let now = Instant::now();
+        assert_eq!(state.try_acquire(now), None);
+        assert_eq!(state.try_acquire(now), None);
+        let wait = state.try_acquire(now).expect("bucket empty");
+        assert!(wait <= Duration::from_secs(30) && wait > Duration::from_secs(29));
+    }
+}
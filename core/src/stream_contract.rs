@@ -5,7 +5,9 @@ use crate::{InferenceEvent, StreamInvariantViolation};
 pub struct EventOrderValidator {
     message_started: bool,
     message_ended: bool,
-    tool_call_started: bool,
+    started_tool_calls: Vec<u32>,
+    started_blocks: Vec<u32>,
+    open_blocks: Vec<u32>,
 }
 
 impl EventOrderValidator {
@@ -31,31 +33,66 @@ impl EventOrderValidator {
             }
             InferenceEvent::MessageDelta { .. }
             | InferenceEvent::ThinkingDelta { .. }
-            | InferenceEvent::ThinkingSignatureDelta { .. } => {
+            | InferenceEvent::ThinkingSignatureDelta { .. }
+            | InferenceEvent::RedactedThinking { .. } => {
                 if !self.message_started {
                     return Err(StreamInvariantViolation::MessageNotStarted);
                 }
             }
-            InferenceEvent::ToolCallStart { .. } => {
+            InferenceEvent::ContentBlockStart { index, .. } => {
                 if !self.message_started {
                     return Err(StreamInvariantViolation::MessageNotStarted);
                 }
-                self.tool_call_started = true;
+                if self.started_blocks.contains(index) {
+                    return Err(StreamInvariantViolation::DuplicateContentBlockStart);
+                }
+                self.started_blocks.push(*index);
+                self.open_blocks.push(*index);
+            }
+            InferenceEvent::ContentBlockStop { index } => {
+                if !self.message_started {
+                    return Err(StreamInvariantViolation::MessageNotStarted);
+                }
+                let Some(position) = self.open_blocks.iter().position(|open| open == index) else {
+                    return Err(StreamInvariantViolation::ContentBlockStopWithoutStart);
+                };
+                self.open_blocks.remove(position);
+            }
+            InferenceEvent::ToolCallStart { index, id, name } => {
+                if !self.message_started {
+                    return Err(StreamInvariantViolation::MessageNotStarted);
+                }
+                if self.started_tool_calls.contains(index) {
+                    return Err(StreamInvariantViolation::DuplicateToolCallStart);
+                }
+                if id.is_empty() {
+                    return Err(StreamInvariantViolation::ToolCallMissingId);
+                }
+                if name.is_empty() {
+                    return Err(StreamInvariantViolation::ToolCallMissingName);
+                }
+                self.started_tool_calls.push(*index);
             }
-            InferenceEvent::ToolCallDelta { .. } => {
+            InferenceEvent::ToolCallDelta { index, .. } => {
                 if !self.message_started {
                     return Err(StreamInvariantViolation::MessageNotStarted);
                 }
-                if !self.tool_call_started {
+                if !self.started_tool_calls.contains(index) {
                     return Err(StreamInvariantViolation::ToolCallDeltaBeforeStart);
                 }
+                if self.started_blocks.contains(index) && !self.open_blocks.contains(index) {
+                    return Err(StreamInvariantViolation::ToolCallDeltaAfterBlockStop);
+                }
             }
             InferenceEvent::MessageEnd { .. } => {
                 if !self.message_started {
                     return Err(StreamInvariantViolation::MessageEndBeforeStart);
                 }
+                if !self.open_blocks.is_empty() {
+                    return Err(StreamInvariantViolation::UnclosedContentBlock);
+                }
                 self.message_ended = true;
-                self.tool_call_started = false;
+                self.started_tool_calls.clear();
             }
         }
 
@@ -0,0 +1,366 @@
+//! `tracing` spans with OpenTelemetry GenAI semantic-convention attributes.
+//!
+//! Provider clients run each `InferenceProvider::complete`/`stream` call in an [`inference_span`]
+//! through [`trace_complete`]/[`trace_stream`];
+//! [`send_with_retry`](crate::http::send_with_retry) adds one child span per HTTP attempt. With
+//! `tracing-opentelemetry` installed, `otel.name`/`otel.kind` become the span name and kind and
+//! the dotted fields become span attributes.
+
+use crate::error::SdkError;
+use crate::{InferenceEvent, InferenceResult, InferenceStream, StopReason, Usage};
+use futures_util::StreamExt;
+use std::future::Future;
+use std::time::Instant;
+use tracing::field::Empty;
+use tracing::{Instrument, Span};
+
+/// Opens the span for one inference call.
+///
+/// `provider` is the `gen_ai.system` value (e.g. `anthropic`, `openai`) and `operation` the
+/// `gen_ai.operation.name` (e.g. `chat`). Response fields start empty and are filled by
+/// [`record_result`], [`instrument_stream`], [`record_error`], and the HTTP layer
+/// (`retry_count`, which counts stream re-issues once a stream has been retried).
+pub fn inference_span(provider: &str, operation: &str, model: &str) -> Span {
+    tracing::info_span!(
+        "gen_ai.inference",
+        otel.name = %format_args!("{operation} {model}"),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        gen_ai.system = provider,
+        gen_ai.operation.name = operation,
+        gen_ai.request.model = model,
+        gen_ai.response.model = Empty,
+        gen_ai.response.finish_reasons = Empty,
+        gen_ai.usage.input_tokens = Empty,
+        gen_ai.usage.output_tokens = Empty,
+        gen_ai.server.time_to_first_token = Empty,
+        retry_count = Empty,
+        error.type = Empty,
+    )
+}
+
+/// Runs a `complete` call inside `span` and records its outcome.
+pub async fn trace_complete<F>(span: Span, call: F) -> Result<InferenceResult, SdkError>
+where
+    F: Future<Output = Result<InferenceResult, SdkError>>,
+{
+    let result = call.instrument(span.clone()).await;
+    match &result {
+        Ok(result) => record_result(&span, result),
+        Err(error) => record_error(&span, error),
+    }
+    result
+}
+
+/// Opens a stream inside `span` and keeps the span open, via [`instrument_stream`], until the
+/// returned stream is dropped.
+pub async fn trace_stream<F>(span: Span, open: F) -> Result<InferenceStream, SdkError>
+where
+    F: Future<Output = Result<InferenceStream, SdkError>>,
+{
+    let started_at = Instant::now();
+    match open.instrument(span.clone()).await {
+        Ok(stream) => Ok(instrument_stream(stream, span, started_at)),
+        Err(error) => {
+            record_error(&span, &error);
+            Err(error)
+        }
+    }
+}
+
+/// Records the response model, stop reason, and token counts of a completed call.
+pub fn record_result(span: &Span, result: &InferenceResult) {
+    span.record("gen_ai.response.model", result.model.as_str());
+    record_end(span, &result.usage, result.stop_reason.as_ref());
+}
+
+/// Records a failed call's error class and marks the span as an error.
+pub fn record_error(span: &Span, error: &SdkError) {
+    span.record("error.type", error_type(error));
+    span.record("otel.status_code", "ERROR");
+}
+
+/// Runs every poll of `stream` inside `span` and fills its response fields as events arrive.
+///
+/// `started_at` is when the call began; the delay until the first content event is recorded as
+/// `gen_ai.server.time_to_first_token` in seconds. The span closes when the stream is dropped.
+pub fn instrument_stream(
+    stream: InferenceStream,
+    span: Span,
+    started_at: Instant,
+) -> InferenceStream {
+    Box::pin(futures_util::stream::unfold(
+        (stream, span, false),
+        move |(mut stream, span, mut first_token_seen)| async move {
+            let item = stream.next().instrument(span.clone()).await?;
+            match &item {
+                Ok(InferenceEvent::MessageStart { model, .. }) => {
+                    span.record("gen_ai.response.model", model.as_str());
+                }
+                Ok(InferenceEvent::MessageEnd { usage, stop_reason }) => {
+                    record_end(&span, usage, stop_reason.as_ref());
+                }
+                Ok(_) => {}
+                Err(error) => record_error(&span, error),
+            }
+            if !first_token_seen && item.as_ref().is_ok_and(is_content) {
+                first_token_seen = true;
+                span.record(
+                    "gen_ai.server.time_to_first_token",
+                    started_at.elapsed().as_secs_f64(),
+                );
+            }
+            Some((item, (stream, span, first_token_seen)))
+        },
+    ))
+}
+
+fn record_end(span: &Span, usage: &Usage, stop_reason: Option<&StopReason>) {
+    span.record("gen_ai.usage.input_tokens", usage.input_tokens);
+    span.record("gen_ai.usage.output_tokens", usage.output_tokens);
+    if let Some(reason) = stop_reason {
+        span.record("gen_ai.response.finish_reasons", finish_reason(reason));
+    }
+}
+
+pub(crate) fn is_content(event: &InferenceEvent) -> bool {
+    matches!(
+        event,
+        InferenceEvent::MessageDelta { .. }
+            | InferenceEvent::ThinkingDelta { .. }
+            | InferenceEvent::RedactedThinking { .. }
+            | InferenceEvent::ToolCallStart { .. }
+    )
+}
+
+fn finish_reason(reason: &StopReason) -> &'static str {
+    match reason {
+        StopReason::EndTurn => "end_turn",
+        StopReason::MaxTokens => "max_tokens",
+        StopReason::ToolUse => "tool_use",
+        StopReason::StopSequence => "stop_sequence",
+        StopReason::Unknown => "unknown",
+    }
+}
+
+/// A low-cardinality `error.type`: the HTTP status for API errors, otherwise the error kind.
+pub(crate) fn error_type(error: &SdkError) -> String {
+    match error {
+        SdkError::ApiStatusError(err) => err.status.to_string(),
+        SdkError::RetriesExhausted { last_error, .. } => error_type(last_error),
+        SdkError::NetworkError(_) => "network_error".to_string(),
+        SdkError::RequestTimeout { .. } | SdkError::TotalTimeoutExceeded { .. } => {
+            "timeout".to_string()
+        }
+        SdkError::StreamIdleTimeout { .. } => "stream_idle_timeout".to_string(),
+        SdkError::Cancelled => "cancelled".to_string(),
+        SdkError::SerializationError(_) => "serialization_error".to_string(),
+        SdkError::ConfigError(_) => "config_error".to_string(),
+        SdkError::StreamError(_) | SdkError::StreamInvariantViolation(_) => {
+            "stream_error".to_string()
+        }
+        SdkError::ProviderError(_) => "provider_error".to_string(),
+        SdkError::UnsupportedParameter { .. } => "unsupported_parameter".to_string(),
+        SdkError::StructuredOutputError(_) => "structured_output_error".to_string(),
+        SdkError::Unknown(_) => "_OTHER".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiStatusError;
+    use crate::http::{
+        EventStream, RequestOptions, RetryConfig, RetryPolicy, StreamItemClass, retry_stream,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+    use tracing_core::span::Current;
+
+    type Fields = HashMap<String, String>;
+
+    /// Keeps every span's fields so tests can inspect what was recorded.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<(&'static Metadata<'static>, Fields)>>>,
+        entered: Arc<Mutex<Vec<Id>>>,
+    }
+
+    impl Recorder {
+        fn span(&self, name: &str) -> Fields {
+            let spans = self.spans.lock().unwrap();
+            let (_, fields) = spans
+                .iter()
+                .find(|(metadata, _)| metadata.name() == name)
+                .expect("span");
+            fields.clone()
+        }
+    }
+
+    struct FieldVisitor<'a>(&'a mut Fields);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Fields::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            match self.entered.lock().unwrap().last() {
+                Some(id) => {
+                    let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1].0;
+                    Current::new(id.clone(), metadata)
+                }
+                None => Current::none(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_span_records_genai_attributes() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let events = vec![
+            Ok(InferenceEvent::MessageStart {
+                role: "assistant".to_string(),
+                model: "model-2024".to_string(),
+                provider_id: "test".to_string(),
+            }),
+            Ok(InferenceEvent::MessageDelta {
+                content: "hi".to_string(),
+            }),
+            Ok(InferenceEvent::MessageEnd {
+                usage: Usage::new(12, 3),
+                stop_reason: Some(StopReason::MaxTokens),
+            }),
+        ];
+        let span = inference_span("test", "chat", "model");
+        let stream = trace_stream(span, async move {
+            Ok(Box::pin(futures_util::stream::iter(events)) as InferenceStream)
+        })
+        .await
+        .unwrap();
+        let collected: Vec<_> = stream.collect().await;
+        assert_eq!(collected.len(), 3);
+
+        let fields = recorder.span("gen_ai.inference");
+        assert_eq!(fields["otel.name"], "chat model");
+        assert_eq!(fields["gen_ai.system"], "test");
+        assert_eq!(fields["gen_ai.request.model"], "model");
+        assert_eq!(fields["gen_ai.response.model"], "model-2024");
+        assert_eq!(fields["gen_ai.usage.input_tokens"], "12");
+        assert_eq!(fields["gen_ai.usage.output_tokens"], "3");
+        assert_eq!(fields["gen_ai.response.finish_reasons"], "max_tokens");
+        assert!(fields.contains_key("gen_ai.server.time_to_first_token"));
+        assert!(!fields.contains_key("error.type"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_are_recorded_on_the_span() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let config = RetryConfig {
+            base_url: String::new(),
+            endpoint: String::new(),
+            default_headers: Default::default(),
+            auth: None,
+            retry_policy: RetryPolicy::default()
+                .with_stream_retry(true)
+                .with_base_delay(Duration::ZERO)
+                .with_jitter(Duration::ZERO),
+            timeout_policy: Default::default(),
+            rate_limiter: None,
+            middleware: Default::default(),
+        };
+        let failed: EventStream<i32> = Box::pin(futures_util::stream::iter(vec![Err(
+            SdkError::StreamError("connection reset".to_string()),
+        )]));
+        let stream = retry_stream(
+            &config,
+            &RequestOptions::default(),
+            Instant::now(),
+            failed,
+            |item| match item {
+                Ok(_) => StreamItemClass::Content,
+                Err(_) => StreamItemClass::Retryable,
+            },
+            |_options| {
+                Box::pin(async {
+                    Ok(Box::pin(futures_util::stream::iter(vec![Ok(1)])) as EventStream<i32>)
+                })
+            },
+        );
+        let span = inference_span("test", "chat", "model");
+        let collected: Vec<_> = stream.collect().instrument(span).await;
+        assert!(matches!(collected[..], [Ok(1)]));
+
+        assert_eq!(recorder.span("gen_ai.inference")["retry_count"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_complete_span_records_error_type() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let span = inference_span("test", "chat", "model");
+        let error = trace_complete(span, async {
+            Err(SdkError::RetriesExhausted {
+                attempts: 3,
+                last_error: Box::new(SdkError::ApiStatusError(Box::new(ApiStatusError::new(
+                    529,
+                    None,
+                    None,
+                    String::new(),
+                )))),
+            })
+        })
+        .await
+        .unwrap_err();
+        assert!(error.is_retryable());
+
+        let fields = recorder.span("gen_ai.inference");
+        assert_eq!(fields["error.type"], "529");
+        assert_eq!(fields["otel.status_code"], "ERROR");
+        assert!(!fields.contains_key("gen_ai.usage.input_tokens"));
+    }
+}
@@ -1,6 +1,6 @@
 use futures_util::stream;
 use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceResult, SdkError, StopReason,
+    InferenceContent, InferenceEvent, InferenceResult, SdkError, StopReason, Usage,
     validate_event_sequence,
 };
 use serde::Deserialize;
@@ -29,8 +29,7 @@ fn build_text_events(delta_count: usize, delta_len: usize) -> Vec<InferenceEvent
     }
 
     events.push(InferenceEvent::MessageEnd {
-        input_tokens: 16,
-        output_tokens: 32,
+        usage: Usage::new(16, 32),
         stop_reason: Some(StopReason::EndTurn),
     });
     events
@@ -44,6 +43,7 @@ fn build_tool_events(payload_len: usize, chunk_len: usize) -> Vec<InferenceEvent
         provider_id: "perf".to_string(),
     });
     events.push(InferenceEvent::ToolCallStart {
+        index: 0,
         id: "call_perf".to_string(),
         name: "store_blob".to_string(),
     });
@@ -52,13 +52,13 @@ fn build_tool_events(payload_len: usize, chunk_len: usize) -> Vec<InferenceEvent
     let json = serde_json::json!({ "payload": payload }).to_string();
     for bytes in json.as_bytes().chunks(chunk_len) {
         events.push(InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: String::from_utf8(bytes.to_vec()).expect("delta chunk must be valid UTF-8"),
         });
     }
 
     events.push(InferenceEvent::MessageEnd {
-        input_tokens: 32,
-        output_tokens: 64,
+        usage: Usage::new(32, 64),
         stop_reason: Some(StopReason::ToolUse),
     });
     events
@@ -84,8 +84,7 @@ fn measure_validate_event_sequence_large_message() -> Duration {
         });
     }
     events.push(InferenceEvent::MessageEnd {
-        input_tokens: 16,
-        output_tokens: 32,
+        usage: Usage::new(16, 32),
         stop_reason: Some(StopReason::EndTurn),
     });
 
@@ -1,7 +1,7 @@
 use futures_util::stream;
 use inference_sdk_core::{
     InferenceContent, InferenceEvent, InferenceResult, SdkError, StopReason,
-    StreamInvariantViolation, validate_event_sequence,
+    StreamInvariantViolation, Usage, validate_event_sequence,
 };
 use proptest::prelude::*;
 
@@ -14,10 +14,9 @@ proptest! {
                 model: "test-model".to_string(),
                 provider_id: "test".to_string(),
             },
-            InferenceEvent::ToolCallDelta { delta },
+            InferenceEvent::ToolCallDelta { index: 0, delta },
             InferenceEvent::MessageEnd {
-                input_tokens: 1,
-                output_tokens: 1,
+                usage: Usage::new(1, 1),
                 stop_reason: Some(StopReason::ToolUse),
             },
         ];
@@ -51,18 +50,18 @@ proptest! {
                 provider_id: "test".to_string(),
             }),
             Ok(InferenceEvent::ToolCallStart {
+                index: 0,
                 id: "call_1".to_string(),
                 name: "weather".to_string(),
             }),
         ];
 
         for delta in deltas {
-            events.push(Ok(InferenceEvent::ToolCallDelta { delta }));
+            events.push(Ok(InferenceEvent::ToolCallDelta { index: 0, delta }));
         }
 
         events.push(Ok(InferenceEvent::MessageEnd {
-            input_tokens: 2,
-            output_tokens: 3,
+            usage: Usage::new(2, 3),
             stop_reason: Some(StopReason::ToolUse),
         }));
 
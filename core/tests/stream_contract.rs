@@ -1,5 +1,6 @@
 use inference_sdk_core::{
-    InferenceEvent, StopReason, StreamInvariantViolation, validate_event_sequence,
+    ContentBlockKind, InferenceEvent, StopReason, StreamInvariantViolation, Usage,
+    validate_event_sequence,
 };
 
 #[test]
@@ -14,15 +15,16 @@ fn test_validate_event_sequence_accepts_valid_order() {
             content: "hello".to_string(),
         },
         InferenceEvent::ToolCallStart {
+            index: 0,
             id: "call_1".to_string(),
             name: "weather".to_string(),
         },
         InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "{\"city\":\"SF\"}".to_string(),
         },
         InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 2,
+            usage: Usage::new(1, 2),
             stop_reason: Some(StopReason::ToolUse),
         },
     ];
@@ -39,11 +41,11 @@ fn test_validate_event_sequence_rejects_tool_delta_before_start() {
             provider_id: "test".to_string(),
         },
         InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "{\"bad\":true}".to_string(),
         },
         InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 2,
+            usage: Usage::new(1, 2),
             stop_reason: Some(StopReason::ToolUse),
         },
     ];
@@ -76,8 +78,7 @@ fn test_validate_event_sequence_rejects_missing_message_end() {
 #[test]
 fn test_validate_event_sequence_rejects_message_end_before_start() {
     let events = vec![InferenceEvent::MessageEnd {
-        input_tokens: 1,
-        output_tokens: 2,
+        usage: Usage::new(1, 2),
         stop_reason: Some(StopReason::EndTurn),
     }];
 
@@ -107,3 +108,155 @@ fn test_validate_event_sequence_rejects_duplicate_message_start() {
         Err(StreamInvariantViolation::DuplicateMessageStart)
     ));
 }
+
+#[test]
+fn test_validate_event_sequence_rejects_delta_for_unstarted_tool_index() {
+    let events = vec![
+        InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        },
+        InferenceEvent::ToolCallStart {
+            index: 0,
+            id: "call_1".to_string(),
+            name: "weather".to_string(),
+        },
+        InferenceEvent::ToolCallDelta {
+            index: 1,
+            delta: "{}".to_string(),
+        },
+    ];
+
+    assert!(matches!(
+        validate_event_sequence(&events),
+        Err(StreamInvariantViolation::ToolCallDeltaBeforeStart)
+    ));
+}
+
+#[test]
+fn test_validate_event_sequence_rejects_duplicate_tool_call_start() {
+    let events = vec![
+        InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        },
+        InferenceEvent::ToolCallStart {
+            index: 0,
+            id: "call_1".to_string(),
+            name: "weather".to_string(),
+        },
+        InferenceEvent::ToolCallStart {
+            index: 0,
+            id: "call_2".to_string(),
+            name: "time".to_string(),
+        },
+    ];
+
+    assert!(matches!(
+        validate_event_sequence(&events),
+        Err(StreamInvariantViolation::DuplicateToolCallStart)
+    ));
+}
+
+#[test]
+fn test_validate_event_sequence_rejects_tool_call_start_without_id_or_name() {
+    let start = |id: &str, name: &str| {
+        vec![
+            InferenceEvent::MessageStart {
+                role: "assistant".to_string(),
+                model: "test-model".to_string(),
+                provider_id: "test".to_string(),
+            },
+            InferenceEvent::ToolCallStart {
+                index: 0,
+                id: id.to_string(),
+                name: name.to_string(),
+            },
+        ]
+    };
+
+    assert!(matches!(
+        validate_event_sequence(&start("", "weather")),
+        Err(StreamInvariantViolation::ToolCallMissingId)
+    ));
+    assert!(matches!(
+        validate_event_sequence(&start("call_1", "")),
+        Err(StreamInvariantViolation::ToolCallMissingName)
+    ));
+}
+
+#[test]
+fn test_validate_event_sequence_rejects_delta_after_block_stop() {
+    let events = vec![
+        InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        },
+        InferenceEvent::ContentBlockStart {
+            index: 0,
+            kind: ContentBlockKind::ToolUse,
+        },
+        InferenceEvent::ToolCallStart {
+            index: 0,
+            id: "call_1".to_string(),
+            name: "weather".to_string(),
+        },
+        InferenceEvent::ContentBlockStop { index: 0 },
+        InferenceEvent::ToolCallDelta {
+            index: 0,
+            delta: "{}".to_string(),
+        },
+    ];
+
+    assert!(matches!(
+        validate_event_sequence(&events),
+        Err(StreamInvariantViolation::ToolCallDeltaAfterBlockStop)
+    ));
+}
+
+#[test]
+fn test_validate_event_sequence_rejects_unclosed_content_block() {
+    let events = vec![
+        InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        },
+        InferenceEvent::ContentBlockStart {
+            index: 0,
+            kind: ContentBlockKind::Text,
+        },
+        InferenceEvent::MessageDelta {
+            content: "hi".to_string(),
+        },
+        InferenceEvent::MessageEnd {
+            usage: Usage::new(1, 1),
+            stop_reason: Some(StopReason::EndTurn),
+        },
+    ];
+
+    assert!(matches!(
+        validate_event_sequence(&events),
+        Err(StreamInvariantViolation::UnclosedContentBlock)
+    ));
+}
+
+#[test]
+fn test_validate_event_sequence_rejects_stop_without_start() {
+    let events = vec![
+        InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        },
+        InferenceEvent::ContentBlockStop { index: 3 },
+    ];
+
+    assert!(matches!(
+        validate_event_sequence(&events),
+        Err(StreamInvariantViolation::ContentBlockStopWithoutStart)
+    ));
+}
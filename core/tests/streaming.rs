@@ -1,7 +1,7 @@
 use futures_util::stream;
 use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceResult, SdkError, StopReason,
-    StreamInvariantViolation,
+    ContentBlockKind, InferenceContent, InferenceEvent, InferenceResult, SdkError, StopReason,
+    StreamInvariantViolation, Usage,
 };
 
 #[tokio::test]
@@ -28,18 +28,20 @@ async fn test_from_stream_accumulates_tool_calls() {
             signature: "_sig_part_2".to_string(),
         }),
         Ok(InferenceEvent::ToolCallStart {
+            index: 0,
             id: tool_id.to_string(),
             name: tool_name.to_string(),
         }),
         Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "{\"loc".to_string(),
         }),
         Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "ation\": \"SF\"}".to_string(),
         }),
         Ok(InferenceEvent::MessageEnd {
-            input_tokens: 10,
-            output_tokens: 20,
+            usage: Usage::new(10, 20),
             stop_reason: Some(StopReason::ToolUse),
         }),
     ];
@@ -87,8 +89,7 @@ async fn test_from_stream_allows_signature_delta_before_thinking_delta() {
             signature: "sig_only".to_string(),
         }),
         Ok(InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 1,
+            usage: Usage::new(1, 1),
             stop_reason: Some(StopReason::EndTurn),
         }),
     ];
@@ -116,15 +117,16 @@ async fn test_from_stream_returns_error_for_invalid_tool_json() {
             provider_id: "test".to_string(),
         }),
         Ok(InferenceEvent::ToolCallStart {
+            index: 0,
             id: "call_123".to_string(),
             name: "weather".to_string(),
         }),
         Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "{\"city\":".to_string(),
         }),
         Ok(InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 2,
+            usage: Usage::new(1, 2),
             stop_reason: Some(StopReason::ToolUse),
         }),
     ];
@@ -165,8 +167,7 @@ async fn test_from_stream_returns_error_when_delta_precedes_message_start() {
             content: "oops".to_string(),
         }),
         Ok(InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 1,
+            usage: Usage::new(1, 1),
             stop_reason: Some(StopReason::EndTurn),
         }),
     ];
@@ -185,8 +186,7 @@ async fn test_from_stream_returns_error_when_delta_precedes_message_start() {
 #[tokio::test]
 async fn test_from_stream_returns_error_when_message_end_precedes_message_start() {
     let events = vec![Ok(InferenceEvent::MessageEnd {
-        input_tokens: 1,
-        output_tokens: 1,
+        usage: Usage::new(1, 1),
         stop_reason: Some(StopReason::EndTurn),
     })];
 
@@ -228,7 +228,7 @@ async fn test_from_stream_returns_error_on_duplicate_message_start() {
 }
 
 #[tokio::test]
-async fn test_from_stream_rolls_tool_calls_on_new_tool_start() {
+async fn test_from_stream_assembles_sequential_tool_calls() {
     let events = vec![
         Ok(InferenceEvent::MessageStart {
             role: "assistant".to_string(),
@@ -236,22 +236,25 @@ async fn test_from_stream_rolls_tool_calls_on_new_tool_start() {
             provider_id: "test".to_string(),
         }),
         Ok(InferenceEvent::ToolCallStart {
+            index: 0,
             id: "call_1".to_string(),
             name: "weather".to_string(),
         }),
         Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
             delta: "{\"city\":\"SF\"}".to_string(),
         }),
         Ok(InferenceEvent::ToolCallStart {
+            index: 1,
             id: "call_2".to_string(),
             name: "time".to_string(),
         }),
         Ok(InferenceEvent::ToolCallDelta {
+            index: 1,
             delta: "{\"timezone\":\"UTC\"}".to_string(),
         }),
         Ok(InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 1,
+            usage: Usage::new(1, 1),
             stop_reason: Some(StopReason::ToolUse),
         }),
     ];
@@ -280,3 +283,161 @@ async fn test_from_stream_rolls_tool_calls_on_new_tool_start() {
     assert_eq!(tool_uses[1].1, "time");
     assert_eq!(tool_uses[1].2["timezone"], "UTC");
 }
+
+#[tokio::test]
+async fn test_from_stream_assembles_interleaved_parallel_tool_calls() {
+    let events = vec![
+        Ok(InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallStart {
+            index: 0,
+            id: "call_1".to_string(),
+            name: "weather".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallStart {
+            index: 1,
+            id: "call_2".to_string(),
+            name: "time".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
+            delta: "{\"city\":".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallDelta {
+            index: 1,
+            delta: "{\"timezone\":".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallDelta {
+            index: 1,
+            delta: "\"UTC\"}".to_string(),
+        }),
+        Ok(InferenceEvent::ToolCallDelta {
+            index: 0,
+            delta: "\"SF\"}".to_string(),
+        }),
+        Ok(InferenceEvent::MessageEnd {
+            usage: Usage::new(1, 1),
+            stop_reason: Some(StopReason::ToolUse),
+        }),
+    ];
+
+    let stream = Box::pin(stream::iter(events));
+    let result = InferenceResult::from_stream(stream)
+        .await
+        .expect("interleaved tool calls should assemble");
+
+    match &result.content[..] {
+        [
+            InferenceContent::ToolUse {
+                id: first_id,
+                input: first_input,
+                ..
+            },
+            InferenceContent::ToolUse {
+                id: second_id,
+                input: second_input,
+                ..
+            },
+        ] => {
+            assert_eq!(first_id, "call_1");
+            assert_eq!(first_input["city"], "SF");
+            assert_eq!(second_id, "call_2");
+            assert_eq!(second_input["timezone"], "UTC");
+        }
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_from_stream_keeps_adjacent_text_blocks_separate() {
+    let events = vec![
+        Ok(InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        }),
+        Ok(InferenceEvent::ContentBlockStart {
+            index: 0,
+            kind: ContentBlockKind::Text,
+        }),
+        Ok(InferenceEvent::MessageDelta {
+            content: "first".to_string(),
+        }),
+        Ok(InferenceEvent::ContentBlockStop { index: 0 }),
+        Ok(InferenceEvent::ContentBlockStart {
+            index: 1,
+            kind: ContentBlockKind::Text,
+        }),
+        Ok(InferenceEvent::MessageDelta {
+            content: "second".to_string(),
+        }),
+        Ok(InferenceEvent::ContentBlockStop { index: 1 }),
+        Ok(InferenceEvent::MessageEnd {
+            usage: Usage::new(1, 1),
+            stop_reason: Some(StopReason::EndTurn),
+        }),
+    ];
+
+    let stream = Box::pin(stream::iter(events));
+    let result = InferenceResult::from_stream(stream)
+        .await
+        .expect("block-delimited text should assemble");
+
+    match &result.content[..] {
+        [
+            InferenceContent::Text { text: first },
+            InferenceContent::Text { text: second },
+        ] => {
+            assert_eq!(first, "first");
+            assert_eq!(second, "second");
+        }
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_from_stream_preserves_redacted_thinking_between_thinking_blocks() {
+    let events = vec![
+        Ok(InferenceEvent::MessageStart {
+            role: "assistant".to_string(),
+            model: "test-model".to_string(),
+            provider_id: "test".to_string(),
+        }),
+        Ok(InferenceEvent::ThinkingDelta {
+            content: "visible".to_string(),
+        }),
+        Ok(InferenceEvent::RedactedThinking {
+            data: "opaque".to_string(),
+        }),
+        Ok(InferenceEvent::ThinkingDelta {
+            content: "more".to_string(),
+        }),
+        Ok(InferenceEvent::MessageEnd {
+            usage: Usage::new(1, 1),
+            stop_reason: Some(StopReason::EndTurn),
+        }),
+    ];
+
+    let stream = Box::pin(stream::iter(events));
+    let result = InferenceResult::from_stream(stream)
+        .await
+        .expect("redacted thinking should assemble");
+
+    match &result.content[..] {
+        [
+            InferenceContent::Thinking { content: first, .. },
+            InferenceContent::RedactedThinking { data },
+            InferenceContent::Thinking {
+                content: second, ..
+            },
+        ] => {
+            assert_eq!(first, "visible");
+            assert_eq!(data, "opaque");
+            assert_eq!(second, "more");
+        }
+        other => panic!("unexpected content: {other:?}"),
+    }
+}
@@ -0,0 +1,107 @@
+use futures_util::stream;
+use inference_sdk_core::{
+    InferenceEvent, InferenceResult, ResponseSchema, SdkError, StopReason, Usage,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Weather {
+    city: String,
+    celsius: i64,
+}
+
+fn weather_schema() -> ResponseSchema {
+    ResponseSchema::new(
+        "weather",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "city": { "type": "string" },
+                "celsius": { "type": "integer" }
+            },
+            "required": ["city", "celsius"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+async fn result_with_text(chunks: &[&str]) -> InferenceResult {
+    let mut events = vec![Ok(InferenceEvent::MessageStart {
+        role: "assistant".to_string(),
+        model: "test-model".to_string(),
+        provider_id: "test".to_string(),
+    })];
+    events.extend(chunks.iter().map(|chunk| {
+        Ok(InferenceEvent::MessageDelta {
+            content: chunk.to_string(),
+        })
+    }));
+    events.push(Ok(InferenceEvent::MessageEnd {
+        usage: Usage::new(1, 1),
+        stop_reason: Some(StopReason::EndTurn),
+    }));
+
+    InferenceResult::from_stream(Box::pin(stream::iter(events)))
+        .await
+        .expect("stream should assemble")
+}
+
+#[tokio::test]
+async fn test_parse_returns_typed_value_matching_schema() {
+    let mut result = result_with_text(&["{\"city\":", "\"Oslo\",\"celsius\":4}"]).await;
+    result.apply_response_schema(weather_schema());
+
+    assert_eq!(
+        result.structured_output,
+        Some(serde_json::json!({ "city": "Oslo", "celsius": 4 }))
+    );
+    assert_eq!(
+        result.parse::<Weather>().expect("output should parse"),
+        Weather {
+            city: "Oslo".to_string(),
+            celsius: 4
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_parse_rejects_output_not_matching_type() {
+    let mut result = result_with_text(&["{\"city\":\"Oslo\",\"celsius\":4}"]).await;
+    result.apply_response_schema(weather_schema());
+
+    let err = result.parse::<Vec<i64>>().expect_err("type mismatch");
+    assert!(
+        matches!(err, SdkError::StructuredOutputError(ref msg) if msg.contains("invalid type")),
+        "unexpected error: {err}"
+    );
+}
+
+#[tokio::test]
+async fn test_parse_rejects_output_violating_schema() {
+    let mut result = result_with_text(&["{\"celsius\":\"cold\",\"wind\":\"strong\"}"]).await;
+    result.apply_response_schema(weather_schema());
+
+    // Any JSON deserializes into `Value`, so only the schema check can reject this.
+    let err = result
+        .parse::<serde_json::Value>()
+        .expect_err("schema violation");
+    let SdkError::StructuredOutputError(msg) = &err else {
+        panic!("unexpected error: {err}");
+    };
+    assert!(msg.contains("does not match the schema"), "{msg}");
+    assert!(msg.contains("\"city\" is a required property"), "{msg}");
+    assert!(msg.contains("/celsius"), "{msg}");
+    assert!(msg.contains("wind"), "{msg}");
+}
+
+#[tokio::test]
+async fn test_parse_reports_non_json_output() {
+    let mut result = result_with_text(&["It is 4 degrees in Oslo."]).await;
+    result.apply_response_schema(weather_schema());
+
+    assert!(result.structured_output.is_none());
+    assert!(matches!(
+        result.parse::<Weather>(),
+        Err(SdkError::StructuredOutputError(ref msg)) if msg.contains("not valid JSON")
+    ));
+}
@@ -1,7 +1,7 @@
 #![no_main]
 
 use futures_util::stream;
-use inference_sdk_core::{InferenceEvent, InferenceResult, SdkError, StopReason};
+use inference_sdk_core::{InferenceEvent, InferenceResult, SdkError, StopReason, Usage};
 use libfuzzer_sys::fuzz_target;
 use std::sync::OnceLock;
 use tokio::runtime::{Builder, Runtime};
@@ -57,17 +57,19 @@ fn synthesize_events(data: &[u8]) -> Vec<Result<InferenceEvent, SdkError>> {
             3 => {
                 tool_seq += 1;
                 events.push(Ok(InferenceEvent::ToolCallStart {
+                    index: (tool_seq - 1) as u32,
                     id: format!("call_{}_{}", idx, tool_seq),
                     name: "tool".to_string(),
                 }));
             }
+            // Alternate between the latest and the previous call to exercise interleaving.
             4 => events.push(Ok(InferenceEvent::ToolCallDelta {
+                index: tool_seq.saturating_sub(1 + u64::from(byte % 2)) as u32,
                 delta: format!("{{\"b\":{}}}", byte),
             })),
             5 => {
                 events.push(Ok(InferenceEvent::MessageEnd {
-                    input_tokens: (idx % 128) as u32,
-                    output_tokens: ((idx + 1) % 128) as u32,
+                    usage: Usage::new((idx % 128) as u32, ((idx + 1) % 128) as u32),
                     stop_reason: Some(StopReason::Unknown),
                 }));
                 break;
@@ -77,6 +79,9 @@ fn synthesize_events(data: &[u8]) -> Vec<Result<InferenceEvent, SdkError>> {
                 model: "dup-model".to_string(),
                 provider_id: "fuzz".to_string(),
             })),
+            7 => events.push(Ok(InferenceEvent::ContentBlockStop {
+                index: tool_seq.saturating_sub(1) as u32,
+            })),
             _ => {}
         }
     }
@@ -85,8 +90,7 @@ fn synthesize_events(data: &[u8]) -> Vec<Result<InferenceEvent, SdkError>> {
         && *last % 3 == 0
     {
         events.push(Ok(InferenceEvent::MessageEnd {
-            input_tokens: 1,
-            output_tokens: 1,
+            usage: Usage::new(1, 1),
             stop_reason: Some(StopReason::EndTurn),
         }));
     }
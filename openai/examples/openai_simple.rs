@@ -1,14 +1,11 @@
 use openai_sdk::{
-    Client,
+    Client, ClientConfig,
     types::chat::{ChatCompletionRequest, ChatContent, ChatMessage, ChatRole},
 };
-use std::env;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let api_key = env::var("OPENAI_API_KEY").expect("OPENAI_API_KEY must be set");
-
-    let client = Client::new(api_key)?;
+    let client = Client::from_config(ClientConfig::from_env()?)?;
 
     let request = ChatCompletionRequest::builder()
         .model("gpt-4o-mini")
@@ -1,7 +1,11 @@
 use inference_sdk_core::SdkError;
-use inference_sdk_core::http::{RetryPolicy, TimeoutPolicy};
-use reqwest::Client as HttpClient;
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use inference_sdk_core::env::{self, EnvLookup};
+use inference_sdk_core::http::{HttpTransport, ReqwestTransport, RetryPolicy, TimeoutPolicy};
+use inference_sdk_core::{
+    AuthHeader, CredentialProvider, Middleware, MiddlewareStack, NetworkConfig, RateLimiter,
+    StaticCredentials,
+};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
 use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
@@ -9,6 +13,12 @@ use std::time::Duration;
 use crate::resources::chat::ChatResource;
 
 const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const ORGANIZATION_HEADER: &str = "openai-organization";
+const PROJECT_HEADER: &str = "openai-project";
+pub const API_KEY_ENV: &str = "OPENAI_API_KEY";
+pub const BASE_URL_ENV: &str = "OPENAI_BASE_URL";
+pub const ORG_ID_ENV: &str = "OPENAI_ORG_ID";
+pub const PROJECT_ID_ENV: &str = "OPENAI_PROJECT_ID";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
@@ -19,16 +29,24 @@ pub struct ClientConfig {
     pub(crate) retry_policy: RetryPolicy,
     pub(crate) timeout_policy: TimeoutPolicy,
     pub(crate) headers: HeaderMap,
+    pub(crate) credentials: Arc<dyn CredentialProvider>,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) middleware: MiddlewareStack,
+    pub(crate) transport: Option<Arc<dyn HttpTransport>>,
+    pub(crate) network: NetworkConfig,
 }
 
 // Manually implement Debug to redact the API key
 impl fmt::Debug for ClientConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ClientConfig")
-            .field("api_key", &"[REDACTED]")
+            .field("credentials", &self.credentials)
             .field("base_url", &self.base_url)
             .field("timeout", &self.timeout)
             .field("max_retries", &self.max_retries)
+            .field("middleware", &self.middleware)
+            .field("transport", &self.transport)
+            .field("network", &self.network)
             .finish()
     }
 }
@@ -36,21 +54,60 @@ impl fmt::Debug for ClientConfig {
 impl ClientConfig {
     pub fn new(api_key: String) -> Result<Self, SdkError> {
         let bearer = format!("Bearer {}", api_key);
-        let auth_value = HeaderValue::from_str(&bearer)
+        HeaderValue::from_str(&bearer)
             .map_err(|e| SdkError::ConfigError(format!("Invalid API key: {}", e)))?;
+        Ok(Self::from_credentials(StaticCredentials::new(api_key)))
+    }
 
+    /// Build a config whose API key is fetched from `credentials` before every HTTP attempt, so
+    /// rotated keys take effect without rebuilding the client.
+    pub fn from_credentials(credentials: impl CredentialProvider) -> Self {
         let mut headers = HeaderMap::new();
-        headers.insert(AUTHORIZATION, auth_value);
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-        Ok(Self {
+        Self {
             base_url: DEFAULT_BASE_URL.to_string(),
             timeout: DEFAULT_TIMEOUT,
             max_retries: 2,
             retry_policy: RetryPolicy::default().with_max_retries(2),
             timeout_policy: TimeoutPolicy::default().with_request_timeout(DEFAULT_TIMEOUT),
             headers,
-        })
+            credentials: Arc::new(credentials),
+            rate_limiter: None,
+            middleware: MiddlewareStack::default(),
+            transport: None,
+            network: NetworkConfig::default(),
+        }
+    }
+
+    /// Build a config from `OPENAI_API_KEY` and, when set, `OPENAI_BASE_URL`, `OPENAI_ORG_ID`,
+    /// and `OPENAI_PROJECT_ID`.
+    pub fn from_env() -> Result<Self, SdkError> {
+        Self::from_env_lookup(&|name| std::env::var(name))
+    }
+
+    fn from_env_lookup(lookup: EnvLookup<'_>) -> Result<Self, SdkError> {
+        let api_key = env::required_var(lookup, API_KEY_ENV)?;
+        let mut config = Self::new(api_key).map_err(|e| env::named_error(API_KEY_ENV, e))?;
+        if let Some(base_url) = env::optional_var(lookup, BASE_URL_ENV)? {
+            config = config.with_base_url(base_url);
+        }
+        if let Some(organization) = env::optional_var(lookup, ORG_ID_ENV)? {
+            config = config
+                .with_organization(&organization)
+                .map_err(|e| env::named_error(ORG_ID_ENV, e))?;
+        }
+        if let Some(project) = env::optional_var(lookup, PROJECT_ID_ENV)? {
+            config = config
+                .with_project(&project)
+                .map_err(|e| env::named_error(PROJECT_ID_ENV, e))?;
+        }
+        Ok(config)
+    }
+
+    /// The `Authorization: Bearer` header resolved from this config's credentials.
+    pub(crate) fn auth_header(&self) -> AuthHeader {
+        AuthHeader::bearer(self.credentials.clone())
     }
 
     pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
@@ -83,11 +140,55 @@ impl ClientConfig {
         self.timeout_policy = policy;
         self
     }
+
+    /// Send `key: value` with every request; per-request headers with the same name override it.
+    pub fn with_header(mut self, key: impl AsRef<str>, value: &str) -> Result<Self, SdkError> {
+        let name = HeaderName::from_bytes(key.as_ref().as_bytes())
+            .map_err(|e| SdkError::ConfigError(e.to_string()))?;
+        let value =
+            HeaderValue::from_str(value).map_err(|e| SdkError::ConfigError(e.to_string()))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Bill requests to `organization` via the `OpenAI-Organization` header.
+    pub fn with_organization(self, organization: &str) -> Result<Self, SdkError> {
+        self.with_header(ORGANIZATION_HEADER, organization)
+    }
+
+    /// Scope requests to `project` via the `OpenAI-Project` header.
+    pub fn with_project(self, project: &str) -> Result<Self, SdkError> {
+        self.with_header(PROJECT_HEADER, project)
+    }
+
+    /// Throttle requests through `limiter`; share one limiter between clients using the same key.
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Add a middleware layer around every HTTP attempt. Layers run in registration order.
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Send requests through `transport` instead of the default reqwest client.
+    pub fn with_transport(mut self, transport: impl HttpTransport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Proxy and TLS settings for the default transport; cannot be combined with `with_transport`.
+    pub fn with_network_config(mut self, network: NetworkConfig) -> Self {
+        self.network = network;
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Client {
-    pub(crate) http_client: HttpClient,
+    pub(crate) transport: Arc<dyn HttpTransport>,
     pub(crate) config: Arc<ClientConfig>,
 }
 
@@ -98,17 +199,19 @@ impl Client {
     }
 
     pub fn from_config(config: ClientConfig) -> Result<Self, SdkError> {
-        let mut builder = HttpClient::builder().default_headers(config.headers.clone());
-        if let Some(timeout) = config.timeout_policy.request_timeout {
-            builder = builder.timeout(timeout);
-        }
-
-        let http_client = builder
-            .build()
-            .map_err(|e| SdkError::ConfigError(format!("Failed to build HTTP client: {}", e)))?;
+        let transport: Arc<dyn HttpTransport> = match &config.transport {
+            Some(_) if !config.network.is_empty() => {
+                return Err(SdkError::ConfigError(
+                    "Network options apply only to the default transport; configure the custom transport instead"
+                        .to_string(),
+                ));
+            }
+            Some(transport) => transport.clone(),
+            None => Arc::new(ReqwestTransport::from_network_config(&config.network)?),
+        };
 
         Ok(Self {
-            http_client,
+            transport,
             config: Arc::new(config),
         })
     }
@@ -123,3 +226,41 @@ impl Client {
         crate::resources::embeddings::Embeddings::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inference_sdk_core::env::lookup_from;
+
+    #[tokio::test]
+    async fn test_from_env_reads_all_variables() {
+        let config = ClientConfig::from_env_lookup(&lookup_from(&[
+            (API_KEY_ENV, "sk-test"),
+            (BASE_URL_ENV, "https://gateway.internal/v1"),
+            (ORG_ID_ENV, "org-123"),
+            (PROJECT_ID_ENV, "proj_456"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.base_url, "https://gateway.internal/v1");
+        assert_eq!(
+            config.auth_header().value().await.unwrap(),
+            "Bearer sk-test"
+        );
+        assert_eq!(config.headers[ORGANIZATION_HEADER], "org-123");
+        assert_eq!(config.headers[PROJECT_HEADER], "proj_456");
+    }
+
+    #[test]
+    fn test_from_env_names_missing_or_invalid_variable() {
+        let err = ClientConfig::from_env_lookup(&lookup_from(&[])).unwrap_err();
+        assert!(err.to_string().contains(API_KEY_ENV), "{err}");
+
+        let err = ClientConfig::from_env_lookup(&lookup_from(&[
+            (API_KEY_ENV, "sk-test"),
+            (ORG_ID_ENV, "bad\norg"),
+        ]))
+        .unwrap_err();
+        assert!(err.to_string().contains(ORG_ID_ENV), "{err}");
+    }
+}
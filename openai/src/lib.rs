@@ -6,21 +6,48 @@ pub mod types;
 pub use client::{Client, ClientConfig};
 use futures_util::{StreamExt, future::BoxFuture};
 pub use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceMessage, InferenceProvider, InferenceRequest,
-    InferenceResult, InferenceRole, InferenceStream, RequestOptions, RetryNetworkRule, RetryPolicy,
-    RetryStatusRule, SdkError, StopReason, TimeoutPolicy, Usage,
+    ApiStatusError, CacheBreakpoint, CancellationToken, CannedResponse, ContentBlockKind,
+    CredentialProvider, EnvCredentials, FileCredentials, HttpTransport, InMemoryTransport,
+    InferenceContent, InferenceEvent, InferenceMessage, InferenceMetrics, InferenceProvider,
+    InferenceRequest, InferenceResult, InferenceRole, InferenceStream, MediaSource, Middleware,
+    MiddlewareStack, NetworkConfig, Next, RateLimitInfo, RateLimiter, RecordedRequest,
+    RefreshingCredentials, RequestOptions, ReqwestTransport, ResponseSchema, RetryNetworkRule,
+    RetryPolicy, RetryStatusRule, SamplingParams, SdkError, StaticCredentials, StopReason,
+    StreamMetrics, TimeoutPolicy, ToolChoice, UnsupportedParameterPolicy, Usage,
 };
+use inference_sdk_core::{metrics, telemetry};
 pub use types::embedding::EmbeddingRequest;
 
 impl InferenceProvider for Client {
-    // Default complete() implementation from trait is used.
+    /// Uses the non-streaming Chat Completions endpoint.
+    fn complete<'a>(
+        &'a self,
+        request: InferenceRequest,
+        options: Option<RequestOptions>,
+    ) -> BoxFuture<'a, Result<InferenceResult, SdkError>> {
+        let span = telemetry::inference_span("openai", "chat", &request.model);
+        Box::pin(telemetry::trace_complete(span, async move {
+            let response_schema = request.response_schema.clone();
+            let metrics = options.as_ref().and_then(|o| o.metrics.clone());
+            let openai_req = normalization::to_openai_request(request)?;
+            let completion = self
+                .chat()
+                .create_with_options(openai_req, options.unwrap_or_default())
+                .await?;
+
+            let result = normalization::from_openai_response(completion)?;
+            Ok(result.finish(response_schema, metrics))
+        }))
+    }
 
     fn stream<'a>(
         &'a self,
         request: InferenceRequest,
         options: Option<RequestOptions>,
     ) -> BoxFuture<'a, Result<InferenceStream, SdkError>> {
-        Box::pin(async move {
+        let span = telemetry::inference_span("openai", "chat", &request.model);
+        Box::pin(telemetry::trace_stream(span, async move {
+            let metrics = options.as_ref().and_then(|o| o.metrics.clone());
             let openai_req = normalization::to_openai_request(request)?;
             let stream = self
                 .chat()
@@ -41,7 +68,7 @@ impl InferenceProvider for Client {
             // Flatten Vec<Result> to Stream
             let flat_stream = mapped_stream.flat_map(futures_util::stream::iter);
 
-            Ok(Box::pin(flat_stream) as InferenceStream)
-        })
+            Ok(metrics::measure_stream(Box::pin(flat_stream), metrics))
+        }))
     }
 }
@@ -1,8 +1,54 @@
 use crate::types;
 use inference_sdk_core::{
-    InferenceContent, InferenceEvent, InferenceRequest, InferenceRole, SdkError, StopReason,
+    ContentBlockKind, InferenceContent, InferenceEvent, InferenceRequest, InferenceResult,
+    InferenceRole, MediaSource, SdkError, StopReason, ToolChoice, Usage,
 };
 
+fn data_url(media_type: &str, data: &str) -> String {
+    format!("data:{media_type};base64,{data}")
+}
+
+fn image_part(source: MediaSource) -> Result<types::chat::ContentPart, SdkError> {
+    let url = match source {
+        MediaSource::Base64 { media_type, data } => data_url(&media_type, &data),
+        MediaSource::Url { url } => url,
+        MediaSource::Text { .. } => {
+            return Err(SdkError::ConfigError(
+                "text media sources are only supported for documents".to_string(),
+            ));
+        }
+    };
+    Ok(types::chat::ContentPart::ImageUrl {
+        image_url: types::chat::ImageUrl { url, detail: None },
+    })
+}
+
+fn document_part(
+    source: MediaSource,
+    title: Option<String>,
+) -> Result<types::chat::ContentPart, SdkError> {
+    match source {
+        MediaSource::Base64 { media_type, data } => Ok(types::chat::ContentPart::File {
+            file: types::chat::FileData {
+                file_data: Some(data_url(&media_type, &data)),
+                file_id: None,
+                filename: title,
+            },
+        }),
+        // Text parts have no filename field, so the title goes in front of the text.
+        MediaSource::Text { data } => Ok(types::chat::ContentPart::Text {
+            text: match title {
+                Some(title) => format!("{title}\n\n{data}"),
+                None => data,
+            },
+        }),
+        MediaSource::Url { .. } => Err(SdkError::ConfigError(
+            "OpenAI chat completions do not accept documents by URL; send base64 data instead"
+                .to_string(),
+        )),
+    }
+}
+
 pub fn to_openai_request(
     req: InferenceRequest,
 ) -> Result<types::chat::ChatCompletionRequest, SdkError> {
@@ -21,22 +67,49 @@ pub fn to_openai_request(
     for msg in req.messages {
         match msg.role {
             InferenceRole::User => {
-                let mut text_parts: Vec<String> = Vec::new();
+                let mut parts: Vec<types::chat::ContentPart> = Vec::new();
                 for content in msg.content {
-                    if let InferenceContent::Text { text } = content {
-                        text_parts.push(text);
+                    match content {
+                        InferenceContent::Text { text } => {
+                            parts.push(types::chat::ContentPart::Text { text });
+                        }
+                        InferenceContent::Image { source } => parts.push(image_part(source)?),
+                        InferenceContent::Document { source, title } => {
+                            parts.push(document_part(source, title)?);
+                        }
+                        _ => {}
                     }
                 }
 
-                if !text_parts.is_empty() {
-                    messages.push(types::chat::ChatMessage {
-                        role: types::chat::ChatRole::User,
-                        content: Some(types::chat::ChatContent::Text(text_parts.join("\n"))),
-                        name: None,
-                        tool_calls: None,
-                        tool_call_id: None,
-                    });
+                if parts.is_empty() {
+                    continue;
                 }
+
+                // Plain-text turns keep the compact string form for maximum compatibility
+                // with OpenAI-compatible servers that do not accept content part arrays.
+                let all_text = parts
+                    .iter()
+                    .all(|p| matches!(p, types::chat::ContentPart::Text { .. }));
+                let content = if all_text {
+                    let text_parts = parts
+                        .into_iter()
+                        .filter_map(|p| match p {
+                            types::chat::ContentPart::Text { text } => Some(text),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    types::chat::ChatContent::Text(text_parts.join("\n"))
+                } else {
+                    types::chat::ChatContent::Parts(parts)
+                };
+
+                messages.push(types::chat::ChatMessage {
+                    role: types::chat::ChatRole::User,
+                    content: Some(content),
+                    name: None,
+                    tool_calls: None,
+                    tool_call_id: None,
+                });
             }
             InferenceRole::Assistant => {
                 let mut text_parts: Vec<String> = Vec::new();
@@ -111,26 +184,155 @@ pub fn to_openai_request(
             .collect()
     });
 
-    let tool_choice = if tools.as_ref().is_some_and(|ts| !ts.is_empty()) {
-        Some(types::chat::ToolChoice::Mode("auto".to_string()))
-    } else {
-        None
+    let has_tools = tools.as_ref().is_some_and(|ts| !ts.is_empty());
+    let tool_choice = match req.tool_choice {
+        Some(ToolChoice::Auto) => Some(types::chat::ToolChoice::Mode("auto".to_string())),
+        Some(ToolChoice::None) => Some(types::chat::ToolChoice::Mode("none".to_string())),
+        Some(ToolChoice::Required) => Some(types::chat::ToolChoice::Mode("required".to_string())),
+        Some(ToolChoice::Tool { name }) => Some(types::chat::ToolChoice::Specific {
+            r#type: "function".to_string(),
+            function: types::chat::ToolChoiceFunction { name },
+        }),
+        None if has_tools => Some(types::chat::ToolChoice::Mode("auto".to_string())),
+        None => None,
     };
+    // OpenAI rejects `parallel_tool_calls` on requests without tools.
+    let parallel_tool_calls = req.parallel_tool_calls.filter(|_| has_tools);
+
+    let sampling = req.sampling.unwrap_or_default();
+    if sampling.top_k.is_some() {
+        req.unsupported_parameters
+            .unwrap_or_default()
+            .check("openai", "top_k")?;
+    }
+
+    let response_format =
+        req.response_schema
+            .map(|schema| types::chat::ResponseFormat::JsonSchema {
+                json_schema: types::chat::JsonSchemaConfig {
+                    name: schema.name,
+                    description: schema.description,
+                    schema: schema.schema,
+                    strict: schema.strict,
+                },
+            });
 
     Ok(types::chat::ChatCompletionRequest::builder()
         .model(req.model)
         .messages(messages)
         .maybe_temperature(req.temperature)
+        .maybe_top_p(sampling.top_p)
         .maybe_max_tokens(req.max_tokens)
+        .maybe_stop(sampling.stop_sequences.map(types::chat::Stop::Multiple))
+        .maybe_seed(sampling.seed)
+        .maybe_presence_penalty(sampling.presence_penalty)
+        .maybe_frequency_penalty(sampling.frequency_penalty)
         .maybe_tools(tools)
         .maybe_tool_choice(tool_choice)
+        .maybe_parallel_tool_calls(parallel_tool_calls)
+        .maybe_response_format(response_format)
         .build())
 }
 
+/// Maps OpenAI usage, whose totals already include cached and reasoning tokens.
+pub(crate) fn to_core_usage(usage: &types::chat::Usage) -> Usage {
+    Usage {
+        input_tokens: usage.prompt_tokens,
+        output_tokens: usage.completion_tokens,
+        cache_creation_input_tokens: None,
+        cache_read_input_tokens: usage
+            .prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens),
+        reasoning_tokens: usage
+            .completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens),
+    }
+}
+
+fn to_stop_reason(finish_reason: &str) -> StopReason {
+    match finish_reason {
+        "stop" => StopReason::EndTurn,
+        "length" => StopReason::MaxTokens,
+        "tool_calls" => StopReason::ToolUse,
+        "content_filter" => StopReason::Unknown,
+        _ => StopReason::Unknown,
+    }
+}
+
+/// Normalizes a non-streaming `ChatCompletion` (its first choice) into an `InferenceResult`.
+pub fn from_openai_response(
+    completion: types::chat::ChatCompletion,
+) -> Result<InferenceResult, SdkError> {
+    let usage = completion
+        .usage
+        .as_ref()
+        .map(to_core_usage)
+        .unwrap_or_default();
+    let Some(choice) = completion.choices.into_iter().next() else {
+        return Err(SdkError::ProviderError(
+            "chat completion contained no choices".to_string(),
+        ));
+    };
+
+    let mut content = Vec::new();
+    match choice.message.content {
+        Some(types::chat::ChatContent::Text(text)) if !text.is_empty() => {
+            content.push(InferenceContent::Text { text });
+        }
+        Some(types::chat::ChatContent::Parts(parts)) => {
+            content.extend(parts.into_iter().filter_map(|part| match part {
+                types::chat::ContentPart::Text { text } => Some(InferenceContent::Text { text }),
+                _ => None,
+            }));
+        }
+        _ => {}
+    }
+    for call in choice.message.tool_calls.unwrap_or_default() {
+        let input = if call.function.arguments.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&call.function.arguments)?
+        };
+        content.push(InferenceContent::ToolUse {
+            id: call.id,
+            name: call.function.name,
+            input,
+        });
+    }
+
+    Ok(InferenceResult {
+        content,
+        model: completion.model,
+        stop_reason: choice.finish_reason.as_deref().map(to_stop_reason),
+        usage,
+        structured_output: None,
+        response_schema: None,
+        rate_limit: completion.rate_limit,
+        metrics: None,
+    })
+}
+
+/// Assembly state for one streamed tool call, keyed by OpenAI's `tool_calls[].index`.
+#[derive(Default)]
+struct ToolCallState {
+    provider_index: u32,
+    block_index: u32,
+    id: Option<String>,
+    name: Option<String>,
+    started: bool,
+    pending_arguments: String,
+}
+
 #[derive(Default)]
 pub struct OpenAiStreamAdapter {
     stop_reason: Option<StopReason>,
     message_started: bool,
+    tool_calls: Vec<ToolCallState>,
+    next_block_index: u32,
+    text_block: Option<u32>,
+    open_blocks: Vec<u32>,
 }
 
 impl OpenAiStreamAdapter {
@@ -138,6 +340,114 @@ impl OpenAiStreamAdapter {
         Self::default()
     }
 
+    /// OpenAI has no block framing, so block indices are assigned here in arrival order.
+    fn open_block(
+        &mut self,
+        kind: ContentBlockKind,
+        events: &mut Vec<Result<InferenceEvent, SdkError>>,
+    ) -> u32 {
+        let index = self.next_block_index;
+        self.next_block_index += 1;
+        self.open_blocks.push(index);
+        events.push(Ok(InferenceEvent::ContentBlockStart { index, kind }));
+        index
+    }
+
+    fn close_text_block(&mut self, events: &mut Vec<Result<InferenceEvent, SdkError>>) {
+        if let Some(index) = self.text_block.take() {
+            self.open_blocks.retain(|open| *open != index);
+            events.push(Ok(InferenceEvent::ContentBlockStop { index }));
+        }
+    }
+
+    /// Closes every open block. Calls that never got both an id and a name cannot be started,
+    /// so their buffered arguments are reported instead of silently dropped.
+    fn close_all_blocks(&mut self, events: &mut Vec<Result<InferenceEvent, SdkError>>) {
+        for call in &mut self.tool_calls {
+            if call.started || call.pending_arguments.is_empty() {
+                continue;
+            }
+            call.pending_arguments.clear();
+            let missing = match (&call.id, &call.name) {
+                (None, None) => "an id and a name",
+                (None, Some(_)) => "an id",
+                _ => "a name",
+            };
+            events.push(Err(SdkError::ProviderError(format!(
+                "tool call at index {} ended without {missing}; its arguments were dropped",
+                call.provider_index
+            ))));
+        }
+        self.text_block = None;
+        for index in self.open_blocks.drain(..) {
+            events.push(Ok(InferenceEvent::ContentBlockStop { index }));
+        }
+    }
+
+    /// Route one tool-call fragment to its call by index.
+    ///
+    /// Parallel calls interleave their fragments, and some compatible servers send the
+    /// name after the id, so arguments are buffered until both id and name are known.
+    /// Calls are told apart by OpenAI's index plus id, which also separates calls on
+    /// servers that reuse `index: 0` for every call with a fresh id.
+    fn process_tool_call(
+        &mut self,
+        tc: &types::chat::ChunkToolCall,
+        events: &mut Vec<Result<InferenceEvent, SdkError>>,
+    ) {
+        let existing = self.tool_calls.iter().rposition(|call| {
+            call.provider_index == tc.index
+                && match (&call.id, &tc.id) {
+                    (Some(known), Some(incoming)) => known == incoming,
+                    _ => true,
+                }
+        });
+        let position = existing.unwrap_or_else(|| {
+            self.tool_calls.push(ToolCallState {
+                provider_index: tc.index,
+                ..ToolCallState::default()
+            });
+            self.tool_calls.len() - 1
+        });
+        let call = &mut self.tool_calls[position];
+
+        if call.id.is_none() {
+            call.id = tc.id.clone();
+        }
+        if let Some(func) = &tc.function {
+            if call.name.is_none() {
+                call.name = func.name.clone();
+            }
+            if let Some(arguments) = &func.arguments {
+                call.pending_arguments.push_str(arguments);
+            }
+        }
+
+        let ready = if !call.started
+            && let (Some(id), Some(name)) = (&call.id, &call.name)
+        {
+            call.started = true;
+            Some((id.clone(), name.clone()))
+        } else {
+            None
+        };
+
+        if let Some((id, name)) = ready {
+            self.close_text_block(events);
+            let index = self.open_block(ContentBlockKind::ToolUse, events);
+            self.tool_calls[position].block_index = index;
+            events.push(Ok(InferenceEvent::ToolCallStart { index, id, name }));
+        }
+
+        let call = &mut self.tool_calls[position];
+        if call.started && !call.pending_arguments.is_empty() {
+            events.push(Ok(InferenceEvent::ToolCallDelta {
+                index: call.block_index,
+                delta: std::mem::take(&mut call.pending_arguments),
+            }));
+        }
+    }
+
     pub fn process_chunk(
         &mut self,
         chunk: types::chat::ChatCompletionChunk,
@@ -146,9 +456,9 @@ impl OpenAiStreamAdapter {
 
         if chunk.choices.is_empty() {
             if let Some(usage) = chunk.usage {
+                self.close_all_blocks(&mut events);
                 events.push(Ok(InferenceEvent::MessageEnd {
-                    input_tokens: usage.prompt_tokens,
-                    output_tokens: usage.completion_tokens,
+                    usage: to_core_usage(&usage),
                     stop_reason: self.stop_reason.clone(),
                 }));
             }
@@ -172,6 +482,10 @@ impl OpenAiStreamAdapter {
         if let Some(content) = &choice.delta.content
             && !content.is_empty()
         {
+            if self.text_block.is_none() {
+                let index = self.open_block(ContentBlockKind::Text, &mut events);
+                self.text_block = Some(index);
+            }
             events.push(Ok(InferenceEvent::MessageDelta {
                 content: content.clone(),
             }));
@@ -179,33 +493,13 @@ impl OpenAiStreamAdapter {
 
         if let Some(tool_calls) = &choice.delta.tool_calls {
             for tc in tool_calls {
-                if let Some(func) = &tc.function {
-                    if let (Some(id), Some(name)) = (&tc.id, &func.name) {
-                        events.push(Ok(InferenceEvent::ToolCallStart {
-                            id: id.clone(),
-                            name: name.clone(),
-                        }));
-                    }
-
-                    if let Some(arguments) = &func.arguments
-                        && !arguments.is_empty()
-                    {
-                        events.push(Ok(InferenceEvent::ToolCallDelta {
-                            delta: arguments.clone(),
-                        }));
-                    }
-                }
+                self.process_tool_call(tc, &mut events);
             }
         }
 
         if let Some(finish_reason) = &choice.finish_reason {
-            self.stop_reason = Some(match finish_reason.as_str() {
-                "stop" => StopReason::EndTurn,
-                "length" => StopReason::MaxTokens,
-                "tool_calls" => StopReason::ToolUse,
-                "content_filter" => StopReason::Unknown,
-                _ => StopReason::Unknown,
-            });
+            self.stop_reason = Some(to_stop_reason(finish_reason));
+            self.close_all_blocks(&mut events);
         }
 
         // Some OpenAI-compatible providers (e.g. MiniMax) emit the final usage chunk
@@ -215,9 +509,9 @@ impl OpenAiStreamAdapter {
             let empty_content = choice.delta.content.as_deref().is_none_or(str::is_empty);
             let no_tool_calls = choice.delta.tool_calls.as_ref().is_none_or(Vec::is_empty);
             if empty_content && no_tool_calls {
+                self.close_all_blocks(&mut events);
                 events.push(Ok(InferenceEvent::MessageEnd {
-                    input_tokens: usage.prompt_tokens,
-                    output_tokens: usage.completion_tokens,
+                    usage: to_core_usage(&usage),
                     stop_reason: self.stop_reason.clone(),
                 }));
             }
@@ -269,6 +563,7 @@ mod tests {
                 prompt_tokens,
                 completion_tokens,
                 total_tokens: prompt_tokens + completion_tokens,
+                ..Default::default()
             }),
             system_fingerprint: None,
         }
@@ -301,6 +596,7 @@ mod tests {
                 prompt_tokens,
                 completion_tokens,
                 total_tokens: prompt_tokens + completion_tokens,
+                ..Default::default()
             }),
             system_fingerprint: None,
         }
@@ -323,14 +619,21 @@ mod tests {
             None,
         );
         let events = adapter.process_chunk(chunk1);
-        assert_eq!(events.len(), 2);
+        assert_eq!(events.len(), 3);
         assert!(matches!(
             events[0],
-            Ok(InferenceEvent::ToolCallStart { ref id, ref name }) if id == "call_123" && name == "weather"
+            Ok(InferenceEvent::ContentBlockStart {
+                index: 0,
+                kind: ContentBlockKind::ToolUse
+            })
         ));
         assert!(matches!(
             events[1],
-            Ok(InferenceEvent::ToolCallDelta { ref delta }) if delta == "{\"loc"
+            Ok(InferenceEvent::ToolCallStart { index: 0, ref id, ref name }) if id == "call_123" && name == "weather"
+        ));
+        assert!(matches!(
+            events[2],
+            Ok(InferenceEvent::ToolCallDelta { index: 0, ref delta }) if delta == "{\"loc"
         ));
 
         let chunk2 = make_choice_chunk(
@@ -349,10 +652,144 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert!(matches!(
             events[0],
-            Ok(InferenceEvent::ToolCallDelta { ref delta }) if delta == "ation\": \"SF\"}"
+            Ok(InferenceEvent::ToolCallDelta { index: 0, ref delta }) if delta == "ation\": \"SF\"}"
+        ));
+    }
+
+    fn tool_fragment(
+        index: u32,
+        id: Option<&str>,
+        name: Option<&str>,
+        args: &str,
+    ) -> ChunkToolCall {
+        ChunkToolCall {
+            index,
+            id: id.map(str::to_string),
+            call_type: id.map(|_| "function".to_string()),
+            function: Some(ChunkFunctionCall {
+                name: name.map(str::to_string),
+                arguments: Some(args.to_string()),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_openai_adapter_routes_interleaved_parallel_tool_calls_by_index() {
+        let mut adapter = OpenAiStreamAdapter::new();
+        let mut events = Vec::new();
+
+        events.extend(adapter.process_chunk(make_choice_chunk(
+            Some(vec![
+                tool_fragment(0, Some("call_a"), Some("weather"), "{\"city\":"),
+                tool_fragment(1, Some("call_b"), Some("time"), "{\"tz\":"),
+            ]),
+            None,
+        )));
+        events.extend(adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(1, None, None, "\"UTC\"}")]),
+            None,
+        )));
+        events.extend(adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(0, None, None, "\"SF\"}")]),
+            Some("tool_calls".to_string()),
+        )));
+
+        let deltas: Vec<(u32, String)> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                Ok(InferenceEvent::ToolCallDelta { index, delta }) => Some((index, delta)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![
+                (0, "{\"city\":".to_string()),
+                (1, "{\"tz\":".to_string()),
+                (1, "\"UTC\"}".to_string()),
+                (0, "\"SF\"}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_openai_adapter_buffers_arguments_until_name_arrives() {
+        let mut adapter = OpenAiStreamAdapter::new();
+
+        let events = adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(0, Some("call_a"), None, "{\"a\":")]),
+            None,
+        ));
+        assert!(events.is_empty());
+
+        let events = adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(0, None, Some("lookup"), "1}")]),
+            None,
+        ));
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            events[0],
+            Ok(InferenceEvent::ContentBlockStart { index: 0, .. })
+        ));
+        assert!(matches!(
+            events[1],
+            Ok(InferenceEvent::ToolCallStart { index: 0, ref name, .. }) if name == "lookup"
+        ));
+        assert!(matches!(
+            events[2],
+            Ok(InferenceEvent::ToolCallDelta { index: 0, ref delta }) if delta == "{\"a\":1}"
+        ));
+    }
+
+    #[test]
+    fn test_openai_adapter_reports_arguments_of_calls_that_never_start() {
+        let mut adapter = OpenAiStreamAdapter::new();
+        let events = adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(1, Some("call_a"), None, "{\"a\":1}")]),
+            None,
+        ));
+        assert!(events.is_empty());
+
+        let events = adapter.process_chunk(make_choice_chunk(None, Some("tool_calls".to_string())));
+        assert!(matches!(
+            &events[..],
+            [Err(SdkError::ProviderError(message))]
+                if message.contains("index 1") && message.contains("without a name")
+        ));
+
+        // Reported once, not again when the usage chunk ends the message.
+        let events = adapter.process_chunk(make_usage_chunk(1, 1));
+        assert!(matches!(
+            &events[..],
+            [Ok(InferenceEvent::MessageEnd { .. })]
         ));
     }
 
+    #[test]
+    fn test_openai_adapter_separates_calls_that_reuse_index_zero() {
+        let mut adapter = OpenAiStreamAdapter::new();
+        let mut events = adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(0, Some("call_a"), Some("one"), "{}")]),
+            None,
+        ));
+        events.extend(adapter.process_chunk(make_choice_chunk(
+            Some(vec![tool_fragment(0, Some("call_b"), Some("two"), "{}")]),
+            None,
+        )));
+
+        let starts: Vec<(u32, String)> = events
+            .into_iter()
+            .filter_map(|e| match e {
+                Ok(InferenceEvent::ToolCallStart { index, id, .. }) => Some((index, id)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            starts,
+            vec![(0, "call_a".to_string()), (1, "call_b".to_string())]
+        );
+    }
+
     #[test]
     fn test_openai_adapter_emits_message_end_from_usage_chunk() {
         let mut adapter = OpenAiStreamAdapter::new();
@@ -366,13 +803,37 @@ mod tests {
         assert!(matches!(
             events[0],
             Ok(InferenceEvent::MessageEnd {
-                input_tokens: 12,
-                output_tokens: 34,
+                ref usage,
                 stop_reason: Some(StopReason::EndTurn)
-            })
+            }) if *usage == inference_sdk_core::Usage::new(12, 34)
         ));
     }
 
+    #[test]
+    fn test_openai_adapter_reports_cached_and_reasoning_tokens() {
+        let mut adapter = OpenAiStreamAdapter::new();
+        let mut usage_chunk = make_usage_chunk(100, 40);
+        if let Some(usage) = usage_chunk.usage.as_mut() {
+            usage.prompt_tokens_details = Some(types::chat::PromptTokensDetails {
+                cached_tokens: Some(64),
+            });
+            usage.completion_tokens_details = Some(types::chat::CompletionTokensDetails {
+                reasoning_tokens: Some(32),
+            });
+        }
+
+        let events = adapter.process_chunk(usage_chunk);
+        let Some(Ok(InferenceEvent::MessageEnd { usage, .. })) = events.last() else {
+            panic!("Expected MessageEnd");
+        };
+        assert_eq!(usage.input_tokens, 100);
+        assert_eq!(usage.output_tokens, 40);
+        assert_eq!(usage.cache_read_input_tokens, Some(64));
+        assert_eq!(usage.cache_creation_input_tokens, None);
+        assert_eq!(usage.reasoning_tokens, Some(32));
+        assert_eq!(usage.total_tokens(), 140);
+    }
+
     #[test]
     fn test_openai_adapter_emits_message_end_from_mixed_usage_chunk() {
         let mut adapter = OpenAiStreamAdapter::new();
@@ -396,9 +857,16 @@ mod tests {
             system_fingerprint: None,
         };
         let ev1 = adapter.process_chunk(start_chunk);
-        assert_eq!(ev1.len(), 2);
+        assert_eq!(ev1.len(), 3);
         assert!(matches!(ev1[0], Ok(InferenceEvent::MessageStart { .. })));
-        assert!(matches!(ev1[1], Ok(InferenceEvent::MessageDelta { .. })));
+        assert!(matches!(
+            ev1[1],
+            Ok(InferenceEvent::ContentBlockStart {
+                index: 0,
+                kind: ContentBlockKind::Text
+            })
+        ));
+        assert!(matches!(ev1[2], Ok(InferenceEvent::MessageDelta { .. })));
 
         let usage_chunk = make_mixed_usage_chunk(
             Some(""),
@@ -409,14 +877,15 @@ mod tests {
             20,
         );
         let ev2 = adapter.process_chunk(usage_chunk);
-        assert_eq!(ev2.len(), 1);
+        assert_eq!(ev2.len(), 2);
         assert!(matches!(
             ev2[0],
-            Ok(InferenceEvent::MessageEnd {
-                input_tokens: 10,
-                output_tokens: 20,
-                ..
-            })
+            Ok(InferenceEvent::ContentBlockStop { index: 0 })
+        ));
+        assert!(matches!(
+            ev2[1],
+            Ok(InferenceEvent::MessageEnd { ref usage, .. })
+                if usage.input_tokens == 10 && usage.output_tokens == 20
         ));
     }
 
@@ -468,8 +937,10 @@ mod tests {
                     text: "hello".to_string(),
                 }],
                 tool_call_id: None,
+                cache_breakpoint: None,
             }],
             system: None,
+            system_cache_breakpoint: None,
             tools: Some(vec![inference_sdk_core::Tool {
                 name: "read_file".to_string(),
                 description: "Read file".to_string(),
@@ -479,9 +950,14 @@ mod tests {
                     "required": ["path"]
                 }),
             }]),
+            tool_choice: None,
+            parallel_tool_calls: None,
             temperature: None,
             max_tokens: None,
             thinking_budget: None,
+            sampling: None,
+            unsupported_parameters: None,
+            response_schema: None,
         };
 
         let out = to_openai_request(req).expect("request normalization");
@@ -489,6 +965,170 @@ mod tests {
             out.tool_choice,
             Some(types::chat::ToolChoice::Mode(ref mode)) if mode == "auto"
         ));
+        assert!(out.parallel_tool_calls.is_none());
+    }
+
+    #[test]
+    fn test_to_openai_request_maps_explicit_tool_choice_and_parallel_toggle() {
+        let req = InferenceRequest::builder()
+            .model("gpt-4o-mini")
+            .messages(vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![InferenceContent::Text {
+                    text: "extract".to_string(),
+                }],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }])
+            .tools(vec![inference_sdk_core::Tool {
+                name: "record".to_string(),
+                description: "Record fields".to_string(),
+                input_schema: serde_json::json!({ "type": "object" }),
+            }])
+            .tool_choice(ToolChoice::Tool {
+                name: "record".to_string(),
+            })
+            .parallel_tool_calls(false)
+            .build();
+
+        let out = to_openai_request(req).expect("request normalization");
+        let json = serde_json::to_value(&out).expect("request should serialize");
+        assert_eq!(
+            json["tool_choice"],
+            serde_json::json!({ "type": "function", "function": { "name": "record" } })
+        );
+        assert_eq!(json["parallel_tool_calls"], false);
+    }
+
+    #[test]
+    fn test_to_openai_request_maps_images_and_documents_to_content_parts() {
+        let req = InferenceRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![
+                    InferenceContent::Text {
+                        text: "What is in these?".to_string(),
+                    },
+                    InferenceContent::Image {
+                        source: MediaSource::Base64 {
+                            media_type: "image/png".to_string(),
+                            data: "iVBORw0KGgo=".to_string(),
+                        },
+                    },
+                    InferenceContent::Image {
+                        source: MediaSource::Url {
+                            url: "https://example.com/cat.jpg".to_string(),
+                        },
+                    },
+                    InferenceContent::Document {
+                        source: MediaSource::Base64 {
+                            media_type: "application/pdf".to_string(),
+                            data: "JVBERi0=".to_string(),
+                        },
+                        title: Some("report.pdf".to_string()),
+                    },
+                ],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }],
+            system: None,
+            system_cache_breakpoint: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            temperature: None,
+            max_tokens: None,
+            thinking_budget: None,
+            sampling: None,
+            unsupported_parameters: None,
+            response_schema: None,
+        };
+
+        let out = to_openai_request(req).expect("request normalization");
+        let json = serde_json::to_value(&out.messages[0]).expect("message should serialize");
+        let parts = &json["content"];
+
+        assert_eq!(parts[0]["type"], "text");
+        assert_eq!(parts[1]["type"], "image_url");
+        assert_eq!(
+            parts[1]["image_url"]["url"],
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+        assert_eq!(parts[2]["image_url"]["url"], "https://example.com/cat.jpg");
+        assert_eq!(parts[3]["type"], "file");
+        assert_eq!(
+            parts[3]["file"]["file_data"],
+            "data:application/pdf;base64,JVBERi0="
+        );
+        assert_eq!(parts[3]["file"]["filename"], "report.pdf");
+    }
+
+    #[test]
+    fn test_to_openai_request_prefixes_text_documents_with_title() {
+        let req = InferenceRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![InferenceContent::Document {
+                    source: MediaSource::Text {
+                        data: "Revenue grew 12%.".to_string(),
+                    },
+                    title: Some("Q3 summary".to_string()),
+                }],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }],
+            system: None,
+            system_cache_breakpoint: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            temperature: None,
+            max_tokens: None,
+            thinking_budget: None,
+            sampling: None,
+            unsupported_parameters: None,
+            response_schema: None,
+        };
+
+        let out = to_openai_request(req).expect("request normalization");
+        let json = serde_json::to_value(&out.messages[0]).expect("message should serialize");
+        assert_eq!(json["content"], "Q3 summary\n\nRevenue grew 12%.");
+    }
+
+    #[test]
+    fn test_to_openai_request_rejects_document_urls() {
+        let req = InferenceRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![InferenceContent::Document {
+                    source: MediaSource::Url {
+                        url: "https://example.com/report.pdf".to_string(),
+                    },
+                    title: None,
+                }],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }],
+            system: None,
+            system_cache_breakpoint: None,
+            tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            temperature: None,
+            max_tokens: None,
+            thinking_budget: None,
+            sampling: None,
+            unsupported_parameters: None,
+            response_schema: None,
+        };
+
+        assert!(matches!(
+            to_openai_request(req),
+            Err(SdkError::ConfigError(_))
+        ));
     }
 
     #[test]
@@ -501,15 +1141,193 @@ mod tests {
                     text: "hello".to_string(),
                 }],
                 tool_call_id: None,
+                cache_breakpoint: None,
             }],
             system: None,
+            system_cache_breakpoint: None,
             tools: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
             temperature: None,
             max_tokens: None,
             thinking_budget: None,
+            sampling: None,
+            unsupported_parameters: None,
+            response_schema: None,
         };
 
         let out = to_openai_request(req).expect("request normalization");
         assert!(out.tool_choice.is_none());
     }
+
+    fn sampling_request(
+        sampling: inference_sdk_core::SamplingParams,
+        policy: Option<inference_sdk_core::UnsupportedParameterPolicy>,
+    ) -> InferenceRequest {
+        InferenceRequest::builder()
+            .model("gpt-4o-mini")
+            .messages(vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![InferenceContent::Text {
+                    text: "hello".to_string(),
+                }],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }])
+            .sampling(sampling)
+            .maybe_unsupported_parameters(policy)
+            .build()
+    }
+
+    #[test]
+    fn test_to_openai_request_maps_sampling_params() {
+        let sampling = inference_sdk_core::SamplingParams::builder()
+            .stop_sequences(vec!["END".to_string()])
+            .top_p(0.5)
+            .seed(42)
+            .presence_penalty(0.25)
+            .frequency_penalty(-0.5)
+            .build();
+        let out =
+            to_openai_request(sampling_request(sampling, None)).expect("request normalization");
+        let json = serde_json::to_value(out).expect("request should serialize");
+
+        assert_eq!(json["stop"], serde_json::json!(["END"]));
+        assert_eq!(json["top_p"], 0.5);
+        assert_eq!(json["seed"], 42);
+        assert_eq!(json["presence_penalty"], 0.25);
+        assert_eq!(json["frequency_penalty"], -0.5);
+    }
+
+    #[test]
+    fn test_to_openai_request_applies_unsupported_parameter_policy_to_top_k() {
+        let sampling = || {
+            inference_sdk_core::SamplingParams::builder()
+                .top_k(40)
+                .build()
+        };
+
+        let out = to_openai_request(sampling_request(
+            sampling(),
+            Some(inference_sdk_core::UnsupportedParameterPolicy::Drop),
+        ))
+        .expect("top_k should be dropped");
+        let json = serde_json::to_value(out).expect("request should serialize");
+        assert!(json.get("top_k").is_none());
+
+        assert!(matches!(
+            to_openai_request(sampling_request(
+                sampling(),
+                Some(inference_sdk_core::UnsupportedParameterPolicy::Error),
+            )),
+            Err(SdkError::UnsupportedParameter { ref parameter, .. }) if parameter == "top_k"
+        ));
+    }
+
+    #[test]
+    fn test_to_openai_request_maps_response_schema_to_response_format() {
+        let mut schema = inference_sdk_core::ResponseSchema::new(
+            "weather",
+            serde_json::json!({ "type": "object" }),
+        );
+        schema.strict = Some(true);
+        let req = InferenceRequest::builder()
+            .model("gpt-4o-mini")
+            .messages(vec![inference_sdk_core::InferenceMessage {
+                role: InferenceRole::User,
+                content: vec![InferenceContent::Text {
+                    text: "weather in Oslo?".to_string(),
+                }],
+                tool_call_id: None,
+                cache_breakpoint: None,
+            }])
+            .response_schema(schema)
+            .build();
+
+        let out = to_openai_request(req).expect("request normalization");
+        let json = serde_json::to_value(out).expect("request should serialize");
+        assert_eq!(
+            json["response_format"],
+            serde_json::json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "weather",
+                    "schema": { "type": "object" },
+                    "strict": true
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_openai_response_maps_text_tool_calls_and_usage() {
+        use crate::types::chat::{
+            ChatCompletion, ChatContent, ChatMessage, ChatRole, Choice, FunctionCall, ToolCall,
+        };
+
+        let completion = ChatCompletion {
+            id: "chatcmpl-1".to_string(),
+            object: "chat.completion".to_string(),
+            created: 0,
+            model: "gpt-4o".to_string(),
+            choices: vec![Choice {
+                index: 0,
+                message: ChatMessage {
+                    role: ChatRole::Assistant,
+                    content: Some(ChatContent::Text("checking".to_string())),
+                    name: None,
+                    tool_calls: Some(vec![ToolCall {
+                        id: "call_1".to_string(),
+                        call_type: "function".to_string(),
+                        function: FunctionCall {
+                            name: "lookup".to_string(),
+                            arguments: "{\"q\":\"oslo\"}".to_string(),
+                        },
+                    }]),
+                    tool_call_id: None,
+                },
+                finish_reason: Some("tool_calls".to_string()),
+                logprobs: None,
+            }],
+            usage: Some(Usage {
+                prompt_tokens: 9,
+                completion_tokens: 3,
+                total_tokens: 12,
+                ..Default::default()
+            }),
+            system_fingerprint: None,
+            rate_limit: None,
+        };
+
+        let out = from_openai_response(completion).expect("response normalization");
+        assert!(matches!(
+            &out.content[0],
+            InferenceContent::Text { text } if text == "checking"
+        ));
+        assert!(matches!(
+            &out.content[1],
+            InferenceContent::ToolUse { id, name, input }
+                if id == "call_1" && name == "lookup" && input["q"] == "oslo"
+        ));
+        assert_eq!(out.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(out.usage, inference_sdk_core::Usage::new(9, 3));
+    }
+
+    #[test]
+    fn test_from_openai_response_rejects_empty_choices() {
+        let completion = crate::types::chat::ChatCompletion {
+            id: "chatcmpl-1".to_string(),
+            object: "chat.completion".to_string(),
+            created: 0,
+            model: "gpt-4o".to_string(),
+            choices: vec![],
+            usage: None,
+            system_fingerprint: None,
+            rate_limit: None,
+        };
+        assert!(matches!(
+            from_openai_response(completion),
+            Err(SdkError::ProviderError(_))
+        ));
+    }
 }
@@ -3,12 +3,14 @@ use crate::types::chat::{
     ChatCompletion, ChatCompletionChunk, ChatCompletionRequest, StreamOptions,
 };
 use eventsource_stream::Eventsource;
-use futures_core::Stream;
 use futures_util::StreamExt;
-use inference_sdk_core::http::{RetryConfig, send_with_retry};
-use inference_sdk_core::{RequestOptions, SdkError};
-use std::pin::Pin;
+use inference_sdk_core::http::{
+    EventStream, RetryConfig, StreamItemClass, cancellable_stream, idle_timeout_stream,
+    metered_bytes, read_json, retry_stream, send_with_retry,
+};
+use inference_sdk_core::{RateLimitInfo, RequestOptions, SdkError};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 
 static REQUEST_DUMP_SEQ: AtomicU64 = AtomicU64::new(1);
 
@@ -60,20 +62,14 @@ impl ChatResource {
         request: ChatCompletionRequest,
         options: RequestOptions,
     ) -> Result<ChatCompletion, SdkError> {
-        let config = RetryConfig {
-            base_url: self.client.config.base_url.clone(),
-            endpoint: "/chat/completions".to_string(),
-            retry_policy: self.client.config.retry_policy.clone(),
-            timeout_policy: self.client.config.timeout_policy.clone(),
-        };
+        let config = self.retry_config();
         maybe_dump_request("create", &self.client.config.base_url, &request);
-        maybe_dump_request("create_stream", &self.client.config.base_url, &request);
         let response =
-            send_with_retry(&self.client.http_client, &config, &request, &options).await?;
-        response
-            .json::<ChatCompletion>()
-            .await
-            .map_err(SdkError::from)
+            send_with_retry(&*self.client.transport, &config, &request, &options).await?;
+        let rate_limit = RateLimitInfo::from_headers(response.headers());
+        let mut body = read_json::<ChatCompletion>(response, &options).await?;
+        body.rate_limit = rate_limit;
+        Ok(body)
     }
 
     /// Create a Chat Completion Stream
@@ -82,59 +78,120 @@ impl ChatResource {
     pub async fn create_stream(
         &self,
         request: ChatCompletionRequest,
-    ) -> Result<
-        Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk, SdkError>> + Send + 'static>>,
-        SdkError,
-    > {
+    ) -> Result<EventStream<ChatCompletionChunk>, SdkError> {
         self.create_stream_with_options(request, RequestOptions::default())
             .await
     }
 
     /// Create a Chat Completion Stream with custom options
+    ///
+    /// With `RetryPolicy::retry_stream_before_first_event`, a stream whose connection fails
+    /// before the first content chunk is transparently re-issued.
     pub async fn create_stream_with_options(
         &self,
         mut request: ChatCompletionRequest,
         options: RequestOptions,
-    ) -> Result<
-        Pin<Box<dyn Stream<Item = Result<ChatCompletionChunk, SdkError>> + Send + 'static>>,
-        SdkError,
-    > {
+    ) -> Result<EventStream<ChatCompletionChunk>, SdkError> {
         request.stream = Some(true);
         request.stream_options = Some(StreamOptions {
             include_usage: true,
         });
 
-        let config = RetryConfig {
+        let config = self.retry_config();
+        maybe_dump_request("create_stream", &self.client.config.base_url, &request);
+        let started_at = Instant::now();
+        let stream = open_stream(&self.client, &config, &request, &options).await?;
+
+        let client = self.client.clone();
+        let reopen_config = config.clone();
+        let stream = retry_stream(
+            &config,
+            &options,
+            started_at,
+            stream,
+            classify_chunk,
+            move |options| {
+                let client = client.clone();
+                let config = reopen_config.clone();
+                let request = request.clone();
+                Box::pin(async move { open_stream(&client, &config, &request, &options).await })
+            },
+        );
+        Ok(cancellable_stream(stream, &options))
+    }
+
+    fn retry_config(&self) -> RetryConfig {
+        RetryConfig {
             base_url: self.client.config.base_url.clone(),
             endpoint: "/chat/completions".to_string(),
+            default_headers: self.client.config.headers.clone(),
+            auth: Some(self.client.config.auth_header()),
             retry_policy: self.client.config.retry_policy.clone(),
             timeout_policy: self.client.config.timeout_policy.clone(),
-        };
-        let response =
-            send_with_retry(&self.client.http_client, &config, &request, &options).await?;
-        let stream = response.bytes_stream().eventsource();
-        let debug_base_url = self.client.config.base_url.clone();
-
-        let mapped_stream = stream.filter_map(move |event_result| {
-            let debug_base_url = debug_base_url.clone();
-            async move {
-                match event_result {
-                    Ok(event) => {
-                        maybe_dump_sse_event(&debug_base_url, &event.event, &event.data);
-                        // OpenAI signals end of stream with `data: [DONE]`
-                        if event.data == "[DONE]" {
-                            return None;
-                        }
-                        Some(
-                            serde_json::from_str::<ChatCompletionChunk>(&event.data)
-                                .map_err(SdkError::SerializationError),
-                        )
+            rate_limiter: self.client.config.rate_limiter.clone(),
+            middleware: self.client.config.middleware.clone(),
+        }
+    }
+}
+
+async fn open_stream(
+    client: &Client,
+    config: &RetryConfig,
+    request: &ChatCompletionRequest,
+    options: &RequestOptions,
+) -> Result<EventStream<ChatCompletionChunk>, SdkError> {
+    let response = send_with_retry(&*client.transport, config, request, options).await?;
+    let stream = metered_bytes(response.bytes_stream(), options).eventsource();
+    let debug_base_url = client.config.base_url.clone();
+
+    let mapped_stream = stream.filter_map(move |event_result| {
+        let debug_base_url = debug_base_url.clone();
+        async move {
+            match event_result {
+                Ok(event) => {
+                    maybe_dump_sse_event(&debug_base_url, &event.event, &event.data);
+                    // OpenAI signals end of stream with `data: [DONE]`
+                    if event.data == "[DONE]" {
+                        return None;
                     }
-                    Err(e) => Some(Err(SdkError::StreamError(e.to_string()))),
+                    Some(
+                        serde_json::from_str::<ChatCompletionChunk>(&event.data)
+                            .map_err(SdkError::SerializationError),
+                    )
                 }
+                Err(e) => Some(Err(SdkError::StreamError(e.to_string()))),
             }
-        });
+        }
+    });
+
+    Ok(idle_timeout_stream(
+        Box::pin(mapped_stream),
+        config,
+        options,
+    ))
+}
 
-        Ok(Box::pin(mapped_stream))
+/// Role-only chunks precede content; transport failures and stalls are worth re-issuing.
+fn classify_chunk(item: &Result<ChatCompletionChunk, SdkError>) -> StreamItemClass {
+    match item {
+        Ok(chunk)
+            if chunk.usage.is_none()
+                && chunk.choices.iter().all(|choice| {
+                    choice.finish_reason.is_none()
+                        && choice
+                            .delta
+                            .content
+                            .as_deref()
+                            .unwrap_or_default()
+                            .is_empty()
+                        && choice.delta.tool_calls.is_none()
+                }) =>
+        {
+            StreamItemClass::Preamble
+        }
+        Err(SdkError::StreamError(_) | SdkError::StreamIdleTimeout { .. }) => {
+            StreamItemClass::Retryable
+        }
+        _ => StreamItemClass::Content,
     }
 }
@@ -1,6 +1,6 @@
 use crate::client::Client;
 use crate::types::embedding::{EmbeddingRequest, EmbeddingResponse};
-use inference_sdk_core::http::{RetryConfig, send_with_retry};
+use inference_sdk_core::http::{RetryConfig, read_json, send_with_retry};
 use inference_sdk_core::{RequestOptions, SdkError};
 
 #[derive(Clone, Debug)]
@@ -28,8 +28,12 @@ impl Embeddings {
         let config = RetryConfig {
             base_url: self.client.config.base_url.clone(),
             endpoint: "/embeddings".to_string(), // Note: base_url is typically "v1", so this becomes "v1/embeddings"
+            default_headers: self.client.config.headers.clone(),
+            auth: Some(self.client.config.auth_header()),
             retry_policy: self.client.config.retry_policy.clone(),
             timeout_policy: self.client.config.timeout_policy.clone(),
+            rate_limiter: self.client.config.rate_limiter.clone(),
+            middleware: self.client.config.middleware.clone(),
         };
 
         // Note: ChatResource sets endpoint to "/chat/completions".
@@ -37,11 +41,8 @@ impl Embeddings {
         // So endpoint should be "/embeddings".
 
         let response =
-            send_with_retry(&self.client.http_client, &config, &request, &options).await?;
+            send_with_retry(&*self.client.transport, &config, &request, &options).await?;
 
-        response
-            .json::<EmbeddingResponse>()
-            .await
-            .map_err(SdkError::from)
+        read_json::<EmbeddingResponse>(response, &options).await
     }
 }
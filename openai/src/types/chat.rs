@@ -1,3 +1,4 @@
+use inference_sdk_core::RateLimitInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -97,6 +98,7 @@ pub enum ChatContent {
 pub enum ContentPart {
     Text { text: String },
     ImageUrl { image_url: ImageUrl },
+    File { file: FileData },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -106,6 +108,18 @@ pub struct ImageUrl {
     pub detail: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FileData {
+    /// Inline file contents as a `data:` URL (e.g. `data:application/pdf;base64,...`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_data: Option<String>,
+    /// ID of a file previously uploaded through the Files API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+}
+
 // ─── Tool Types ──────────────────────────────────────────────────
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -166,6 +180,9 @@ pub struct ChatCompletion {
     pub choices: Vec<Choice>,
     pub usage: Option<Usage>,
     pub system_fingerprint: Option<String>,
+    /// Rate-limit headers of the HTTP response; not part of the JSON body.
+    #[serde(skip)]
+    pub rate_limit: Option<RateLimitInfo>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -176,11 +193,29 @@ pub struct Choice {
     pub logprobs: Option<serde_json::Value>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens_details: Option<PromptTokensDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens_details: Option<CompletionTokensDetails>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PromptTokensDetails {
+    /// Prompt tokens served from the prompt cache (included in `prompt_tokens`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_tokens: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CompletionTokensDetails {
+    /// Tokens spent on reasoning (included in `completion_tokens`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_tokens: Option<u32>,
 }
 
 // ─── Streaming Types ─────────────────────────────────────────────
@@ -213,3 +213,682 @@ async fn test_debug_redacts_api_key() {
         "Debug output should show [REDACTED]"
     );
 }
+
+#[tokio::test]
+async fn test_inference_complete_uses_non_streaming_endpoint() {
+    use openai_sdk::{
+        InferenceContent, InferenceMessage, InferenceProvider, InferenceRequest, InferenceRole,
+        StopReason,
+    };
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "chatcmpl-complete",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "Hello from JSON" },
+                "finish_reason": "stop",
+                "logprobs": null
+            }],
+            "usage": { "prompt_tokens": 5, "completion_tokens": 3, "total_tokens": 8 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri()),
+    )
+    .unwrap();
+
+    let request = InferenceRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![InferenceMessage {
+            role: InferenceRole::User,
+            content: vec![InferenceContent::Text {
+                text: "Hello!".to_string(),
+            }],
+            tool_call_id: None,
+            cache_breakpoint: None,
+        }])
+        .build();
+
+    let result = client
+        .complete(request, None)
+        .await
+        .expect("complete should succeed");
+
+    assert_eq!(result.text(), "Hello from JSON");
+    assert_eq!(result.stop_reason, Some(StopReason::EndTurn));
+    assert_eq!(result.usage.total_tokens(), 8);
+}
+
+#[tokio::test]
+async fn test_context_length_error_is_structured() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .insert_header("x-request-id", "req_xyz")
+                .set_body_json(json!({
+                    "error": {
+                        "message": "This model's maximum context length is 128000 tokens.",
+                        "type": "invalid_request_error",
+                        "param": "messages",
+                        "code": "context_length_exceeded"
+                    }
+                })),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri()),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+
+    let err = client.chat().create(request).await.unwrap_err();
+    assert!(err.is_context_length_exceeded());
+    assert!(!err.is_retryable());
+
+    let status = err.api_status().expect("structured API error");
+    assert_eq!(status.status, 400);
+    assert_eq!(status.error_type.as_deref(), Some("invalid_request_error"));
+    assert_eq!(status.code.as_deref(), Some("context_length_exceeded"));
+    assert_eq!(status.request_id.as_deref(), Some("req_xyz"));
+}
+
+#[tokio::test]
+async fn test_cancellation_aborts_in_flight_request() {
+    use openai_sdk::{CancellationToken, RequestOptions, SdkError};
+    use std::time::{Duration, Instant};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(10)))
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri()),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+
+    let token = CancellationToken::new();
+    let canceller = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        canceller.cancel();
+    });
+
+    let started = Instant::now();
+    let result = client
+        .chat()
+        .create_with_options(request, RequestOptions::new().with_cancellation(token))
+        .await;
+
+    assert!(matches!(result, Err(SdkError::Cancelled)), "{result:?}");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_rate_limiter_waits_for_reported_reset() {
+    use openai_sdk::RateLimiter;
+    use std::time::{Duration, Instant};
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ratelimit-limit-requests", "1")
+                .insert_header("x-ratelimit-remaining-requests", "0")
+                .insert_header("x-ratelimit-reset-requests", "300ms")
+                .set_body_json(json!({
+                    "id": "chatcmpl-limited",
+                    "object": "chat.completion",
+                    "created": 1677652288,
+                    "model": "gpt-4o",
+                    "choices": [{
+                        "index": 0,
+                        "message": { "role": "assistant", "content": "ok" },
+                        "finish_reason": "stop",
+                        "logprobs": null
+                    }]
+                })),
+        )
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri())
+            .with_rate_limiter(RateLimiter::new()),
+    )
+    .unwrap();
+
+    let request = || {
+        ChatCompletionRequest::builder()
+            .model("gpt-4o")
+            .messages(vec![ChatMessage {
+                role: ChatRole::User,
+                content: Some(ChatContent::Text("Hello!".to_string())),
+                name: None,
+                tool_calls: None,
+                tool_call_id: None,
+            }])
+            .build()
+    };
+
+    let first = client
+        .chat()
+        .create(request())
+        .await
+        .expect("first request");
+    let info = first.rate_limit.expect("rate-limit headers");
+    assert_eq!(info.requests_remaining, Some(0));
+    assert_eq!(info.requests_reset, Some(Duration::from_millis(300)));
+
+    let started = Instant::now();
+    client
+        .chat()
+        .create(request())
+        .await
+        .expect("second request");
+    assert!(
+        started.elapsed() >= Duration::from_millis(250),
+        "second request should wait for the window to reset"
+    );
+}
+
+/// Adds a header to every attempt and counts the responses it sees.
+struct SigningMiddleware {
+    attempts: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl openai_sdk::Middleware for SigningMiddleware {
+    fn handle<'a>(
+        &'a self,
+        mut request: reqwest::Request,
+        next: openai_sdk::Next<'a>,
+    ) -> futures_util::future::BoxFuture<'a, Result<reqwest::Response, openai_sdk::SdkError>> {
+        Box::pin(async move {
+            request
+                .headers_mut()
+                .insert("x-signature", "signed".parse().unwrap());
+            let response = next.run(request).await?;
+            self.attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(response)
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_each_attempt_for_chat_and_embeddings() {
+    use openai_sdk::EmbeddingRequest;
+    use std::sync::atomic::Ordering;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .and(header("x-signature", "signed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "chatcmpl-mw",
+            "object": "chat.completion",
+            "created": 1677652288,
+            "model": "gpt-4o",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "ok" },
+                "finish_reason": "stop",
+                "logprobs": null
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/embeddings"))
+        .and(header("x-signature", "signed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "data": [{ "object": "embedding", "embedding": [0.1, 0.2], "index": 0 }],
+            "model": "text-embedding-3-small",
+            "usage": { "prompt_tokens": 2, "total_tokens": 2 }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let attempts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri())
+            .with_retry_policy(
+                openai_sdk::RetryPolicy::default()
+                    .with_base_delay(std::time::Duration::from_millis(1))
+                    .with_jitter(std::time::Duration::ZERO),
+            )
+            .with_middleware(SigningMiddleware {
+                attempts: attempts.clone(),
+            }),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+    client.chat().create(request).await.expect("chat request");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    let embedding = EmbeddingRequest::builder()
+        .input("hello".to_string())
+        .model("text-embedding-3-small".to_string())
+        .build();
+    client
+        .embeddings()
+        .create(embedding)
+        .await
+        .expect("embeddings request");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+/// Rejects every request before it reaches the network.
+struct DenyAll;
+
+impl openai_sdk::Middleware for DenyAll {
+    fn handle<'a>(
+        &'a self,
+        _request: reqwest::Request,
+        _next: openai_sdk::Next<'a>,
+    ) -> futures_util::future::BoxFuture<'a, Result<reqwest::Response, openai_sdk::SdkError>> {
+        Box::pin(async {
+            Err(openai_sdk::SdkError::ConfigError(
+                "blocked by policy".to_string(),
+            ))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_can_short_circuit_without_retrying() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_base_url(mock_server.uri())
+            .with_middleware(DenyAll),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+    let error = client.chat().create(request).await.unwrap_err();
+    assert!(
+        matches!(&error, openai_sdk::SdkError::ConfigError(message) if message == "blocked by policy"),
+        "unexpected error: {error:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_in_memory_transport_replays_canned_responses() {
+    use openai_sdk::{CannedResponse, InMemoryTransport};
+
+    let transport = InMemoryTransport::new()
+        .with_response(CannedResponse::new(503, "busy"))
+        .with_response(CannedResponse::json(
+            200,
+            &json!({
+                "id": "chatcmpl-mem",
+                "object": "chat.completion",
+                "created": 1677652288,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": "from memory" },
+                    "finish_reason": "stop",
+                    "logprobs": null
+                }]
+            }),
+        ));
+
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_retry_policy(
+                openai_sdk::RetryPolicy::default()
+                    .with_base_delay(std::time::Duration::from_millis(1))
+                    .with_jitter(std::time::Duration::ZERO),
+            )
+            .with_transport(transport.clone()),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+    let completion = client.chat().create(request).await.expect("chat request");
+    assert_eq!(completion.id, "chatcmpl-mem");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let last = &requests[1];
+    assert_eq!(
+        last.url.as_str(),
+        "https://api.openai.com/v1/chat/completions"
+    );
+    assert_eq!(last.headers["authorization"], "Bearer test-key");
+    assert_eq!(last.headers["content-type"], "application/json");
+    let body: serde_json::Value = serde_json::from_slice(&last.body).unwrap();
+    assert_eq!(body["model"], "gpt-4o");
+
+    let error = client
+        .embeddings()
+        .create(
+            openai_sdk::EmbeddingRequest::builder()
+                .input("hello".to_string())
+                .model("text-embedding-3-small".to_string())
+                .build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, openai_sdk::SdkError::ConfigError(_)),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_organization_project_and_default_headers_are_sent() {
+    use openai_sdk::{CannedResponse, InMemoryTransport, RequestOptions};
+
+    let completion = json!({
+        "id": "chatcmpl-org",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "ok" },
+            "finish_reason": "stop",
+            "logprobs": null
+        }]
+    });
+    let transport = InMemoryTransport::new()
+        .with_response(CannedResponse::json(200, &completion))
+        .with_response(CannedResponse::json(200, &completion));
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_organization("org-123")
+            .unwrap()
+            .with_project("proj_456")
+            .unwrap()
+            .with_header("x-tenant", "acme")
+            .unwrap()
+            .with_transport(transport.clone()),
+    )
+    .unwrap();
+
+    let request = ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![ChatMessage {
+            role: ChatRole::User,
+            content: Some(ChatContent::Text("Hello!".to_string())),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }])
+        .build();
+    client.chat().create(request.clone()).await.unwrap();
+    client
+        .chat()
+        .create_with_options(
+            request,
+            RequestOptions::new()
+                .with_header("x-tenant", "other")
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let requests = transport.requests();
+    let first = &requests[0].headers;
+    assert_eq!(first["openai-organization"], "org-123");
+    assert_eq!(first["openai-project"], "proj_456");
+    assert_eq!(first["x-tenant"], "acme");
+    let second = &requests[1].headers;
+    assert_eq!(second["x-tenant"], "other");
+    assert_eq!(second.get_all("x-tenant").iter().count(), 1);
+    assert_eq!(second["openai-organization"], "org-123");
+
+    assert!(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_organization("bad\norg")
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_rotated_credentials_apply_to_next_request() {
+    use openai_sdk::{CannedResponse, InMemoryTransport, RefreshingCredentials};
+    use std::sync::{Arc, Mutex};
+
+    let embedding = json!({
+        "object": "list",
+        "data": [{ "object": "embedding", "index": 0, "embedding": [0.1] }],
+        "model": "text-embedding-3-small",
+        "usage": { "prompt_tokens": 1, "total_tokens": 1 }
+    });
+    let transport = InMemoryTransport::new()
+        .with_response(CannedResponse::json(200, &embedding))
+        .with_response(CannedResponse::json(200, &embedding));
+    let current_key = Arc::new(Mutex::new("sk-old".to_string()));
+    let source = current_key.clone();
+    let credentials = RefreshingCredentials::new(move || {
+        let key = source.lock().unwrap().clone();
+        async move { Ok(key) }
+    })
+    .with_ttl(std::time::Duration::ZERO);
+    let config = openai_sdk::client::ClientConfig::from_credentials(credentials)
+        .with_transport(transport.clone());
+    assert!(format!("{config:?}").contains("RefreshingCredentials"));
+    let client = Client::from_config(config).unwrap();
+
+    let request = || {
+        openai_sdk::EmbeddingRequest::builder()
+            .input("hello".to_string())
+            .model("text-embedding-3-small".to_string())
+            .build()
+    };
+    client.embeddings().create(request()).await.unwrap();
+    *current_key.lock().unwrap() = "sk-new".to_string();
+    client.embeddings().create(request()).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests[0].headers["authorization"], "Bearer sk-old");
+    assert_eq!(requests[1].headers["authorization"], "Bearer sk-new");
+}
+
+#[tokio::test]
+async fn test_complete_metrics_record_body_and_response_time() {
+    use openai_sdk::{
+        CannedResponse, InMemoryTransport, InferenceContent, InferenceMessage, InferenceProvider,
+        InferenceRequest, InferenceRole, RequestOptions, StreamMetrics,
+    };
+
+    let body = json!({
+        "id": "chatcmpl-metrics",
+        "object": "chat.completion",
+        "created": 1677652288,
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "Hello" },
+            "finish_reason": "stop",
+            "logprobs": null
+        }],
+        "usage": { "prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5 }
+    });
+    let transport = InMemoryTransport::new().with_response(CannedResponse::json(200, &body));
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_transport(transport),
+    )
+    .unwrap();
+
+    let request = InferenceRequest::builder()
+        .model("gpt-4o")
+        .messages(vec![InferenceMessage {
+            role: InferenceRole::User,
+            content: vec![InferenceContent::Text {
+                text: "Hi".to_string(),
+            }],
+            tool_call_id: None,
+            cache_breakpoint: None,
+        }])
+        .max_tokens(16)
+        .build();
+    let metrics = StreamMetrics::new();
+    let result = client
+        .complete(
+            request,
+            Some(RequestOptions::new().with_metrics(metrics.clone())),
+        )
+        .await
+        .unwrap();
+
+    let recorded = result.metrics.expect("metrics attached");
+    assert_eq!(recorded.bytes_received, body.to_string().len() as u64);
+    assert_eq!(recorded.output_tokens, Some(2));
+    let first_token = recorded.time_to_first_token.expect("response time");
+    assert!(recorded.duration.unwrap() >= first_token);
+}
+
+#[tokio::test]
+async fn test_insufficient_quota_is_not_retried() {
+    use openai_sdk::{CannedResponse, InMemoryTransport};
+
+    let quota = json!({
+        "error": {
+            "message": "You exceeded your current quota",
+            "type": "insufficient_quota",
+            "param": null,
+            "code": "insufficient_quota"
+        }
+    });
+    let transport = InMemoryTransport::new()
+        .with_response(CannedResponse::json(429, &quota))
+        .with_response(CannedResponse::json(429, &quota));
+    let client = Client::from_config(
+        openai_sdk::client::ClientConfig::new("test-key".to_string())
+            .unwrap()
+            .with_retry_policy(
+                openai_sdk::RetryPolicy::default()
+                    .with_base_delay(std::time::Duration::ZERO)
+                    .with_jitter(std::time::Duration::ZERO),
+            )
+            .with_transport(transport.clone()),
+    )
+    .unwrap();
+
+    let error = client
+        .embeddings()
+        .create(
+            openai_sdk::EmbeddingRequest::builder()
+                .input("hello".to_string())
+                .model("text-embedding-3-small".to_string())
+                .build(),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, openai_sdk::SdkError::ApiStatusError(err) if err.is_quota_exhausted()),
+        "{error:?}"
+    );
+    assert!(!error.is_rate_limit());
+    assert!(!error.is_retryable());
+    assert_eq!(transport.requests().len(), 1);
+}
@@ -1,4 +1,6 @@
-use inference_sdk_core::{InferenceEvent, SdkError, StopReason, validate_event_sequence};
+use inference_sdk_core::{
+    ContentBlockKind, InferenceEvent, SdkError, StopReason, validate_event_sequence,
+};
 use openai_sdk::normalization::OpenAiStreamAdapter;
 use openai_sdk::types::chat::{
     ChatCompletionChunk, ChatRole, ChunkChoice, ChunkDelta, ChunkFunctionCall, ChunkToolCall, Usage,
@@ -97,6 +99,7 @@ fn test_openai_provider_contract_tool_stream_order_and_message_end() {
             prompt_tokens: 11,
             completion_tokens: 22,
             total_tokens: 33,
+            ..Default::default()
         }),
         Some(vec![]),
     );
@@ -106,16 +109,35 @@ fn test_openai_provider_contract_tool_stream_order_and_message_end() {
     validate_event_sequence(&events).expect("event sequence must satisfy core contract");
 
     assert!(matches!(events[0], InferenceEvent::MessageStart { .. }));
-    assert!(matches!(events[1], InferenceEvent::ToolCallStart { .. }));
-    assert!(matches!(events[2], InferenceEvent::ToolCallDelta { .. }));
-    assert!(matches!(events[3], InferenceEvent::ToolCallDelta { .. }));
+    assert!(matches!(
+        events[1],
+        InferenceEvent::ContentBlockStart {
+            index: 0,
+            kind: ContentBlockKind::ToolUse
+        }
+    ));
+    assert!(matches!(
+        events[2],
+        InferenceEvent::ToolCallStart { index: 0, .. }
+    ));
+    assert!(matches!(
+        events[3],
+        InferenceEvent::ToolCallDelta { index: 0, .. }
+    ));
     assert!(matches!(
         events[4],
+        InferenceEvent::ToolCallDelta { index: 0, .. }
+    ));
+    assert!(matches!(
+        events[5],
+        InferenceEvent::ContentBlockStop { index: 0 }
+    ));
+    assert!(matches!(
+        events[6],
         InferenceEvent::MessageEnd {
-            input_tokens: 11,
-            output_tokens: 22,
+            ref usage,
             stop_reason: Some(StopReason::ToolUse)
-        }
+        } if *usage == inference_sdk_core::Usage::new(11, 22)
     ));
 }
 
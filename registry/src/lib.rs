@@ -1,16 +1,20 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use inference_sdk_core::{InferenceProvider, SdkError};
+use inference_sdk_core::env::{self, EnvLookup};
+use inference_sdk_core::{InferenceProvider, NetworkConfig, SdkError};
 use thiserror::Error;
 
 type FactoryFn =
     dyn Fn(&ProviderInit) -> Result<Arc<dyn InferenceProvider>, RegistryError> + Send + Sync;
+type EnvFactoryFn = dyn Fn() -> Result<Arc<dyn InferenceProvider>, RegistryError> + Send + Sync;
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ProviderInit {
     pub api_key: String,
     pub base_url: Option<String>,
+    /// Proxy and TLS settings for the provider's HTTP client.
+    pub network: NetworkConfig,
 }
 
 impl ProviderInit {
@@ -18,6 +22,7 @@ impl ProviderInit {
         Self {
             api_key: api_key.into(),
             base_url: None,
+            network: NetworkConfig::default(),
         }
     }
 
@@ -25,6 +30,24 @@ impl ProviderInit {
         self.base_url = Some(base_url.into());
         self
     }
+
+    pub fn with_network_config(mut self, network: NetworkConfig) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Reads `<DRIVER>_API_KEY` and, when set, `<DRIVER>_BASE_URL`, where `<DRIVER>` is the
+    /// upper-cased driver name with other characters replaced by `_` (`my-llm` → `MY_LLM`).
+    pub fn from_env(driver: &str) -> Result<Self, SdkError> {
+        Self::from_env_lookup(driver, &|name| std::env::var(name))
+    }
+
+    fn from_env_lookup(driver: &str, lookup: EnvLookup<'_>) -> Result<Self, SdkError> {
+        let prefix = env_prefix(driver);
+        let mut init = Self::new(env::required_var(lookup, &format!("{prefix}_API_KEY"))?);
+        init.base_url = env::optional_var(lookup, &format!("{prefix}_BASE_URL"))?;
+        Ok(init)
+    }
 }
 
 #[derive(Debug, Error)]
@@ -45,6 +68,7 @@ pub enum RegistryError {
 #[derive(Clone, Default)]
 pub struct ProviderRegistry {
     factories: HashMap<String, Arc<FactoryFn>>,
+    env_factories: HashMap<String, Arc<EnvFactoryFn>>,
 }
 
 impl ProviderRegistry {
@@ -56,15 +80,31 @@ impl ProviderRegistry {
         let mut registry = Self::new();
         registry.register("openai", Arc::new(openai_factory));
         registry.register("anthropic", Arc::new(anthropic_factory));
+        registry.register_env("openai", Arc::new(openai_env_factory));
+        registry.register_env("anthropic", Arc::new(anthropic_env_factory));
         registry
     }
 
+    /// Registers `factory` for `driver`, replacing any previous factory and dropping the
+    /// driver's environment factory.
     pub fn register(
         &mut self,
         driver: impl Into<String>,
         factory: Arc<FactoryFn>,
     ) -> Option<Arc<FactoryFn>> {
-        self.factories
+        let key = normalize_driver(driver.into());
+        self.env_factories.remove(&key);
+        self.factories.insert(key, factory)
+    }
+
+    /// Registers how `create_from_env` builds `driver` when the `ProviderInit::from_env`
+    /// convention does not fit (e.g. extra provider-specific variables).
+    pub fn register_env(
+        &mut self,
+        driver: impl Into<String>,
+        factory: Arc<EnvFactoryFn>,
+    ) -> Option<Arc<EnvFactoryFn>> {
+        self.env_factories
             .insert(normalize_driver(driver.into()), factory)
     }
 
@@ -89,6 +129,29 @@ impl ProviderRegistry {
             })?;
         factory(init)
     }
+
+    /// Creates `driver` from environment variables: the builtin drivers use their
+    /// `ClientConfig::from_env`, other drivers [`ProviderInit::from_env`].
+    pub fn create_from_env(
+        &self,
+        driver: &str,
+    ) -> Result<Arc<dyn InferenceProvider>, RegistryError> {
+        let key = normalize_driver(driver.to_string());
+        if let Some(factory) = self.env_factories.get(&key) {
+            return factory();
+        }
+        if !self.factories.contains_key(&key) {
+            return Err(RegistryError::UnknownDriver {
+                driver: driver.to_string(),
+                available: self.drivers(),
+            });
+        }
+        let init = ProviderInit::from_env(&key).map_err(|source| RegistryError::Init {
+            driver: key.clone(),
+            source,
+        })?;
+        self.create(&key, &init)
+    }
 }
 
 pub fn create_provider(
@@ -102,6 +165,20 @@ fn normalize_driver(driver: String) -> String {
     driver.trim().to_ascii_lowercase()
 }
 
+fn env_prefix(driver: &str) -> String {
+    driver
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 fn openai_factory(init: &ProviderInit) -> Result<Arc<dyn InferenceProvider>, RegistryError> {
     let mut config = openai_sdk::ClientConfig::new(init.api_key.clone()).map_err(|source| {
         RegistryError::Init {
@@ -113,6 +190,7 @@ fn openai_factory(init: &ProviderInit) -> Result<Arc<dyn InferenceProvider>, Reg
     if let Some(base_url) = &init.base_url {
         config = config.with_base_url(base_url.clone());
     }
+    config = config.with_network_config(init.network.clone());
 
     let client = openai_sdk::Client::from_config(config).map_err(|source| RegistryError::Init {
         driver: "openai".to_string(),
@@ -121,6 +199,26 @@ fn openai_factory(init: &ProviderInit) -> Result<Arc<dyn InferenceProvider>, Reg
     Ok(Arc::new(client))
 }
 
+fn openai_env_factory() -> Result<Arc<dyn InferenceProvider>, RegistryError> {
+    let client = openai_sdk::ClientConfig::from_env()
+        .and_then(openai_sdk::Client::from_config)
+        .map_err(|source| RegistryError::Init {
+            driver: "openai".to_string(),
+            source,
+        })?;
+    Ok(Arc::new(client))
+}
+
+fn anthropic_env_factory() -> Result<Arc<dyn InferenceProvider>, RegistryError> {
+    let client = anthropic_sdk::ClientConfig::from_env()
+        .and_then(anthropic_sdk::Client::from_config)
+        .map_err(|source| RegistryError::Init {
+            driver: "anthropic".to_string(),
+            source,
+        })?;
+    Ok(Arc::new(client))
+}
+
 fn anthropic_factory(init: &ProviderInit) -> Result<Arc<dyn InferenceProvider>, RegistryError> {
     let mut config = anthropic_sdk::ClientConfig::new(init.api_key.clone()).map_err(|source| {
         RegistryError::Init {
@@ -132,6 +230,7 @@ fn anthropic_factory(init: &ProviderInit) -> Result<Arc<dyn InferenceProvider>,
     if let Some(base_url) = &init.base_url {
         config = config.with_base_url(base_url.clone());
     }
+    config = config.with_network_config(init.network.clone());
 
     let client =
         anthropic_sdk::Client::from_config(config).map_err(|source| RegistryError::Init {
@@ -170,6 +269,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn invalid_network_config_fails_provider_init() {
+        let init = ProviderInit::new("test-key")
+            .with_network_config(NetworkConfig::new().with_root_certificate_pem("not a pem"));
+        let err = match create_provider("anthropic", &init) {
+            Ok(_) => panic!("invalid certificate should fail"),
+            Err(err) => err,
+        };
+
+        match err {
+            RegistryError::Init { driver, source } => {
+                assert_eq!(driver, "anthropic");
+                assert!(matches!(source, SdkError::ConfigError(_)), "{source:?}");
+            }
+            other => panic!("unexpected error variant: {other}"),
+        }
+    }
+
+    #[test]
+    fn provider_init_from_env_uses_driver_prefix() {
+        let lookup = env::lookup_from(&[
+            ("MY_LLM_API_KEY", "key"),
+            ("MY_LLM_BASE_URL", "https://llm.internal/v1"),
+        ]);
+        let init = ProviderInit::from_env_lookup("my-llm", &lookup).unwrap();
+        assert_eq!(init.api_key, "key");
+        assert_eq!(init.base_url.as_deref(), Some("https://llm.internal/v1"));
+
+        let err = ProviderInit::from_env_lookup("other", &lookup).unwrap_err();
+        assert!(err.to_string().contains("OTHER_API_KEY"), "{err}");
+    }
+
+    #[test]
+    fn create_from_env_rejects_unknown_driver() {
+        let registry = ProviderRegistry::with_builtin_drivers();
+        assert!(matches!(
+            registry.create_from_env("unknown"),
+            Err(RegistryError::UnknownDriver { .. })
+        ));
+    }
+
     #[test]
     fn create_openai_provider_succeeds() {
         let provider = create_provider("openai", &ProviderInit::new("test-key"));